@@ -0,0 +1,418 @@
+//! `ray-dash`: a command-line client for the Ray job submission API.
+
+use std::{
+    collections::HashMap,
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process::ExitCode,
+    time::Duration,
+};
+
+use clap::{Parser, Subcommand, ValueEnum};
+use futures_timer::Delay;
+use ray_dashboard_sdk::{
+    JobSubmissionAPI, RayDashboardClient, WaitOptions,
+    schemas::{
+        env::RuntimeEnv,
+        jobs::{JobDetails, JobStatus, JobSubmitRequest},
+    },
+};
+
+const DEFAULT_ADDRESS: &str = "http://127.0.0.1:8265";
+const FOLLOW_INTERVAL_MS: u64 = 1000;
+
+#[derive(Debug, Parser)]
+#[command(name = "ray-dash", version, about = "Submit and manage Ray jobs")]
+struct Cli {
+    /// Address of the Ray dashboard
+    #[arg(long, env = "RAY_ADDRESS", default_value = DEFAULT_ADDRESS, global = true)]
+    address: String,
+
+    /// Output format
+    #[arg(long, value_enum, default_value_t = OutputFormat::Table, global = true)]
+    output: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum OutputFormat {
+    Table,
+    Json,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Submit a job, then wait for it and print its logs unless `--no-wait` is set.
+    /// With JSON output, the logs are printed to stderr.
+    Submit(SubmitArgs),
+    /// Print the status of a job
+    Status { submission_id: String },
+    /// Print the logs of a job
+    Logs {
+        submission_id: String,
+        /// Keep printing new output until the job finishes
+        #[arg(long, short)]
+        follow: bool,
+    },
+    /// List all jobs
+    List,
+    /// Stop a job
+    Stop { submission_id: String },
+    /// Delete a finished job
+    Delete { submission_id: String },
+    /// Wait for a job to finish and print its final status
+    Wait {
+        submission_id: String,
+        /// Maximum number of seconds to wait
+        #[arg(long)]
+        timeout: Option<u64>,
+    },
+    /// Print the Ray version of the cluster
+    Version,
+}
+
+#[derive(Debug, clap::Args)]
+struct SubmitArgs {
+    /// Submission ID of the job, generated by Ray if not set
+    #[arg(long)]
+    submission_id: Option<String>,
+
+    /// Runtime environment file in YAML or JSON format
+    #[arg(long)]
+    runtime_env: Option<PathBuf>,
+
+    /// Local directory uploaded as the working directory, overriding the runtime environment file
+    #[arg(long)]
+    working_dir: Option<PathBuf>,
+
+    /// Metadata attached to the job, as `key=value`. Repeated keys keep the last value
+    #[arg(long, value_parser = parse_key_value)]
+    metadata: Vec<(String, String)>,
+
+    #[arg(long)]
+    entrypoint_num_cpus: Option<f64>,
+
+    #[arg(long)]
+    entrypoint_num_gpus: Option<f64>,
+
+    /// Return after submitting instead of waiting for the job to finish
+    #[arg(long)]
+    no_wait: bool,
+
+    /// Command run by the job. Each argument is shell-quoted, so it reaches the job unchanged
+    #[arg(last = true, required = true)]
+    entrypoint: Vec<String>,
+}
+
+fn parse_key_value(value: &str) -> Result<(String, String), String> {
+    match value.split_once('=') {
+        Some(("", _)) => Err(format!("Expected a non-empty key, got {}", value)),
+        Some((key, value)) => Ok((key.to_string(), value.to_string())),
+        None => Err(format!("Expected key=value, got {}", value)),
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let cli = Cli::parse();
+    match run(cli).await {
+        Ok(code) => code,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+async fn run(cli: Cli) -> ray_dashboard_sdk::Result<ExitCode> {
+    let client = RayDashboardClient::new(&cli.address)?;
+    let output = cli.output;
+
+    match cli.command {
+        Command::Submit(args) => {
+            let payload = build_submit_request(&args)?;
+            let submission_id = client.submit_job(&payload).await?.submission_id;
+            if args.no_wait {
+                print_json_or(
+                    output,
+                    &serde_json::json!({ "submission_id": submission_id }),
+                    || println!("{}", submission_id),
+                );
+                return Ok(ExitCode::SUCCESS);
+            }
+
+            eprintln!("Submitted job {}", submission_id);
+            let details = match output {
+                OutputFormat::Json => follow_logs(&client, &submission_id, io::stderr()).await?,
+                OutputFormat::Table => follow_logs(&client, &submission_id, io::stdout()).await?,
+            };
+            print_details(output, &details);
+            Ok(exit_code(&details))
+        }
+        Command::Status { submission_id } => {
+            let details = client.get_job_details(&submission_id).await?;
+            print_details(output, &details);
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Logs {
+            submission_id,
+            follow,
+        } => {
+            if follow {
+                follow_logs(&client, &submission_id, io::stdout()).await?;
+            } else {
+                let logs = client.get_job_logs(&submission_id).await?;
+                print_json_or(output, &logs, || print!("{}", logs.logs));
+            }
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::List => {
+            let jobs = client.list_jobs().await?;
+            print_json_or(output, &jobs, || print_table(&jobs));
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Stop { submission_id } => {
+            let response = client.stop_job(&submission_id).await?;
+            print_json_or(output, &response, || match response.stopped {
+                true => println!("Stopped job {}", submission_id),
+                false => println!("Job {} was not running", submission_id),
+            });
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Delete { submission_id } => {
+            let response = client.delete_job(&submission_id).await?;
+            print_json_or(output, &response, || println!("Deleted job {}", submission_id));
+            Ok(ExitCode::SUCCESS)
+        }
+        Command::Wait {
+            submission_id,
+            timeout,
+        } => {
+            let mut options = WaitOptions::new();
+            if let Some(timeout) = timeout {
+                options = options.with_timeout(Duration::from_secs(timeout));
+            }
+            let details = client
+                .wait_for_terminal_with_options(&submission_id, &options)
+                .await?;
+            print_details(output, &details);
+            Ok(exit_code(&details))
+        }
+        Command::Version => {
+            let version = client.get_version().await?;
+            print_json_or(output, &version, || println!("{}", version.ray_version));
+            Ok(ExitCode::SUCCESS)
+        }
+    }
+}
+
+fn build_submit_request(args: &SubmitArgs) -> ray_dashboard_sdk::Result<JobSubmitRequest> {
+    let (program, program_args) = args
+        .entrypoint
+        .split_first()
+        .expect("Entrypoint is required by clap");
+    let mut payload = JobSubmitRequest::from_command(program, program_args);
+    payload.submission_id = args.submission_id.clone();
+    payload.entrypoint_num_cpus = args.entrypoint_num_cpus;
+    payload.entrypoint_num_gpus = args.entrypoint_num_gpus;
+    if !args.metadata.is_empty() {
+        payload.metadata = Some(args.metadata.iter().cloned().collect::<HashMap<_, _>>());
+    }
+
+    let mut runtime_env = match args.runtime_env {
+        Some(ref path) => Some(read_runtime_env(path)?),
+        None => None,
+    };
+    if let Some(ref working_dir) = args.working_dir {
+        runtime_env = Some(runtime_env.unwrap_or_default().with_working_dir(working_dir));
+    }
+    payload.runtime_env = runtime_env;
+
+    Ok(payload)
+}
+
+/// Read a runtime environment file. JSON is a subset of YAML, so both are parsed as YAML.
+fn read_runtime_env(path: &Path) -> ray_dashboard_sdk::Result<RuntimeEnv> {
+    let contents = std::fs::read_to_string(path)?;
+    serde_norway::from_str(&contents).map_err(|e| {
+        ray_dashboard_sdk::Error::Generic(format!(
+            "Invalid runtime environment file {}: {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Write new log output until the job reaches a terminal state.
+async fn follow_logs(
+    client: &RayDashboardClient,
+    submission_id: &str,
+    mut out: impl Write,
+) -> ray_dashboard_sdk::Result<JobDetails> {
+    let mut printed = 0;
+    loop {
+        let details = client.get_job_details(submission_id).await?;
+        let logs = client.get_job_logs(submission_id).await?.logs;
+        if let Some(new_output) = logs.get(printed..) {
+            out.write_all(new_output.as_bytes())?;
+            out.flush()?;
+        }
+        printed = logs.len();
+
+        if details.status.is_terminal() {
+            return Ok(details);
+        }
+        Delay::new(Duration::from_millis(FOLLOW_INTERVAL_MS)).await;
+    }
+}
+
+fn exit_code(details: &JobDetails) -> ExitCode {
+    match details.status {
+        JobStatus::SUCCEEDED => ExitCode::SUCCESS,
+        _ => ExitCode::FAILURE,
+    }
+}
+
+fn print_json_or<T: serde::Serialize>(output: OutputFormat, value: &T, print_text: impl FnOnce()) {
+    match output {
+        OutputFormat::Json => match serde_json::to_string_pretty(value) {
+            Ok(json) => println!("{}", json),
+            Err(e) => eprintln!("Error: {}", e),
+        },
+        OutputFormat::Table => print_text(),
+    }
+}
+
+fn print_details(output: OutputFormat, details: &JobDetails) {
+    print_json_or(output, details, || print_table(std::slice::from_ref(details)));
+}
+
+fn print_table(jobs: &[JobDetails]) {
+    let rows: Vec<[String; 4]> = jobs
+        .iter()
+        .map(|job| {
+            let id = job
+                .submission_id
+                .clone()
+                .or(job.driver_info.as_ref().map(|info| info.id.clone()))
+                .unwrap_or_default();
+            let duration = job
+                .duration()
+                .map(|duration| format!("{:.1}s", duration.as_secs_f64()))
+                .unwrap_or_default();
+            [id, format!("{:?}", job.status), duration, job.entrypoint.clone()]
+        })
+        .collect();
+
+    let header = ["SUBMISSION ID", "STATUS", "DURATION", "ENTRYPOINT"].map(String::from);
+    let mut widths = header.clone().map(|column| column.len());
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    for row in std::iter::once(&header).chain(&rows) {
+        println!(
+            "{:<w0$}  {:<w1$}  {:<w2$}  {}",
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submit_args(args: &[&str]) -> SubmitArgs {
+        let cli = Cli::try_parse_from(["ray-dash", "submit"].iter().chain(args)).unwrap();
+        match cli.command {
+            Command::Submit(args) => args,
+            command => panic!("Expected submit, got {:?}", command),
+        }
+    }
+
+    #[test]
+    fn test_parse_key_value() {
+        assert_eq!(
+            parse_key_value("team=ml=ops").unwrap(),
+            ("team".to_string(), "ml=ops".to_string())
+        );
+        assert_eq!(
+            parse_key_value("team=").unwrap(),
+            ("team".to_string(), String::new())
+        );
+        assert!(parse_key_value("team").is_err());
+        assert!(parse_key_value("=ml").is_err());
+        assert!(Cli::try_parse_from(["ray-dash", "submit", "--metadata", "=ml", "--", "ls"]).is_err());
+    }
+
+    #[test]
+    fn test_build_submit_request() {
+        let args = submit_args(&[
+            "--submission-id",
+            "job-1",
+            "--metadata",
+            "team=ml",
+            "--metadata",
+            "team=ops",
+            "--entrypoint-num-cpus",
+            "2",
+            "--working-dir",
+            "/tmp/project",
+            "--",
+            "python",
+            "train.py",
+        ]);
+        let payload = build_submit_request(&args).unwrap();
+
+        assert_eq!(payload.entrypoint, "python train.py");
+        assert_eq!(payload.submission_id.as_deref(), Some("job-1"));
+        assert_eq!(payload.entrypoint_num_cpus, Some(2.0));
+        let metadata = payload.metadata.unwrap();
+        assert_eq!(metadata.len(), 1);
+        assert_eq!(metadata["team"], "ops");
+        let runtime_env = payload.runtime_env.unwrap();
+        assert_eq!(runtime_env.working_dir, Some(PathBuf::from("/tmp/project")));
+    }
+
+    #[test]
+    fn test_build_submit_request_quotes_arguments() {
+        let args = submit_args(&["--", "python", "-c", "print('a b')"]);
+        let payload = build_submit_request(&args).unwrap();
+        assert_eq!(payload.entrypoint, r"python -c 'print('\''a b'\'')'");
+    }
+
+    #[test]
+    fn test_build_submit_request_reads_runtime_env() {
+        let temp_file = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(temp_file.path(), "pip:\n  - requests\nworking_dir: /tmp/env\n").unwrap();
+        let runtime_env = temp_file.path().to_str().unwrap();
+
+        let payload =
+            build_submit_request(&submit_args(&["--runtime-env", runtime_env, "--", "ls"])).unwrap();
+        let env = payload.runtime_env.unwrap();
+        assert_eq!(env.pip, Some(vec!["requests".to_string()]));
+        assert_eq!(env.working_dir, Some(PathBuf::from("/tmp/env")));
+
+        let args = submit_args(&[
+            "--runtime-env",
+            runtime_env,
+            "--working-dir",
+            "/tmp/cli",
+            "--",
+            "ls",
+        ]);
+        let env = build_submit_request(&args).unwrap().runtime_env.unwrap();
+        assert_eq!(env.working_dir, Some(PathBuf::from("/tmp/cli")));
+        assert_eq!(env.pip, Some(vec!["requests".to_string()]));
+    }
+}
@@ -0,0 +1,188 @@
+//! Blocking client for synchronous code.
+//!
+//! Enabled with the `blocking` feature. Each call runs the async client to completion on an
+//! internal single-threaded Tokio runtime, so methods must not be called from async code.
+
+use std::{path::Path, sync::Arc, time::Duration};
+
+use tokio::runtime::Runtime;
+
+use crate::{
+    JobOutcome, JobRetryPolicy, JobSubmissionAPI, PackagesAPI, RetryReport, RunOptions, WaitOptions,
+    client::bulk::{BulkJobReport, JobSelector},
+    schemas::{
+        capabilities::ServerCapabilities,
+        common::{ClusterHealth, RayVersionResponse},
+        jobs::{
+            JobDeleteResponse, JobDetails, JobLogsResponse, JobStatus, JobStopResponse, JobSubmitRequest,
+            JobSubmitResponse,
+        },
+        query::JobQuery,
+    },
+};
+
+/// Blocking counterpart of [`crate::RayDashboardClient`].
+/// Clones share the same connection pool and runtime.
+#[derive(Debug, Clone)]
+pub struct RayDashboardClient {
+    inner: crate::RayDashboardClient,
+    runtime: Arc<Runtime>,
+}
+
+impl RayDashboardClient {
+    pub fn new(base_url: &str) -> crate::Result<Self> {
+        Self::from_async(crate::RayDashboardClient::new(base_url)?)
+    }
+
+    pub fn new_with_client(base_url: &str, client: reqwest::Client) -> crate::Result<Self> {
+        Self::from_async(crate::RayDashboardClient::new_with_client(base_url, client)?)
+    }
+
+    /// Wrap an async client.
+    pub fn from_async(inner: crate::RayDashboardClient) -> crate::Result<Self> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        Ok(Self {
+            inner,
+            runtime: Arc::new(runtime),
+        })
+    }
+
+    /// The wrapped async client.
+    pub fn as_async(&self) -> &crate::RayDashboardClient {
+        &self.inner
+    }
+
+    pub fn ping(&self) -> crate::Result<()> {
+        self.runtime.block_on(self.inner.ping())
+    }
+
+    pub fn get_version(&self) -> crate::Result<RayVersionResponse> {
+        self.runtime.block_on(self.inner.get_version())
+    }
+
+    /// Report which optional APIs the connected server supports, based on its version.
+    pub fn server_capabilities(&self) -> crate::Result<ServerCapabilities> {
+        self.runtime.block_on(self.inner.server_capabilities())
+    }
+
+    /// Check the health of the dashboard, GCS and the raylet on the dashboard's node.
+    pub fn health(&self) -> crate::Result<ClusterHealth> {
+        self.runtime.block_on(self.inner.health())
+    }
+
+    /// Block until the cluster reports healthy, or return an error after the timeout.
+    pub fn wait_until_ready(&self, timeout: Duration) -> crate::Result<ClusterHealth> {
+        self.runtime.block_on(self.inner.wait_until_ready(timeout))
+    }
+
+    /// Submit a new job
+    pub fn submit_job(&self, payload: &JobSubmitRequest) -> crate::Result<JobSubmitResponse> {
+        self.runtime.block_on(self.inner.submit_job(payload))
+    }
+
+    /// List all jobs
+    pub fn list_jobs(&self) -> crate::Result<Vec<JobDetails>> {
+        self.runtime.block_on(self.inner.list_jobs())
+    }
+
+    /// List jobs matching a query
+    pub fn query_jobs(&self, query: &JobQuery) -> crate::Result<Vec<JobDetails>> {
+        self.runtime.block_on(self.inner.query_jobs(query))
+    }
+
+    /// Get job details by submission ID
+    pub fn get_job_details(&self, submission_id: &str) -> crate::Result<JobDetails> {
+        self.runtime.block_on(self.inner.get_job_details(submission_id))
+    }
+
+    /// Get job status by fetching details and then extracting status
+    pub fn get_job_status(&self, submission_id: &str) -> crate::Result<JobStatus> {
+        self.runtime.block_on(self.inner.get_job_status(submission_id))
+    }
+
+    /// Delete a job
+    pub fn delete_job(&self, submission_id: &str) -> crate::Result<JobDeleteResponse> {
+        self.runtime.block_on(self.inner.delete_job(submission_id))
+    }
+
+    /// Stop a running job
+    pub fn stop_job(&self, submission_id: &str) -> crate::Result<JobStopResponse> {
+        self.runtime.block_on(self.inner.stop_job(submission_id))
+    }
+
+    /// Stop the selected jobs with bounded concurrency, skipping jobs that are already terminal
+    pub fn stop_jobs(&self, selector: &JobSelector, concurrency: usize) -> crate::Result<BulkJobReport> {
+        self.runtime.block_on(self.inner.stop_jobs(selector, concurrency))
+    }
+
+    /// Delete the selected jobs with bounded concurrency, skipping jobs that are not yet terminal
+    pub fn delete_jobs(&self, selector: &JobSelector, concurrency: usize) -> crate::Result<BulkJobReport> {
+        self.runtime
+            .block_on(self.inner.delete_jobs(selector, concurrency))
+    }
+
+    /// Get the logs for a job
+    pub fn get_job_logs(&self, submission_id: &str) -> crate::Result<JobLogsResponse> {
+        self.runtime.block_on(self.inner.get_job_logs(submission_id))
+    }
+
+    /// Wait for the job to reach a terminal state and return its final details
+    pub fn wait_for_terminal(
+        &self,
+        submission_id: &str,
+        max_duration: Option<Duration>,
+    ) -> crate::Result<JobDetails> {
+        self.runtime
+            .block_on(self.inner.wait_for_terminal(submission_id, max_duration))
+    }
+
+    /// Wait for the job to reach a terminal state with configurable polling and return its final details
+    pub fn wait_for_terminal_with_options(
+        &self,
+        submission_id: &str,
+        options: &WaitOptions,
+    ) -> crate::Result<JobDetails> {
+        self.runtime
+            .block_on(self.inner.wait_for_terminal_with_options(submission_id, options))
+    }
+
+    /// Submit a job, wait for it to finish, and collect its final details and logs.
+    pub fn run_job(&self, payload: &JobSubmitRequest, options: RunOptions) -> crate::Result<JobOutcome> {
+        self.runtime.block_on(self.inner.run_job(payload, options))
+    }
+
+    /// Run a job, resubmitting it while the retry policy allows.
+    pub fn run_job_with_retry(
+        &self,
+        payload: &JobSubmitRequest,
+        options: RunOptions,
+        policy: &JobRetryPolicy,
+    ) -> RetryReport {
+        self.runtime
+            .block_on(self.inner.run_job_with_retry(payload, options, policy))
+    }
+
+    /// Check if a package exists on the Ray cluster
+    pub fn package_exists(&self, package_uri: &str) -> crate::Result<bool> {
+        self.runtime.block_on(self.inner.package_exists(package_uri))
+    }
+
+    /// Upload a package to the Ray cluster
+    pub fn upload_package(&self, package_uri: &str, data: Vec<u8>) -> crate::Result<()> {
+        self.runtime
+            .block_on(self.inner.upload_package(package_uri, data))
+    }
+
+    /// Upload a directory as a package to the Ray cluster (always respects .gitignore)
+    pub fn upload_directory(&self, directory: &Path) -> crate::Result<String> {
+        self.runtime.block_on(self.inner.upload_directory(directory))
+    }
+
+    /// Upload a directory if it doesn't already exist (always respects .gitignore)
+    pub fn upload_directory_if_needed(&self, directory: &Path) -> crate::Result<String> {
+        self.runtime
+            .block_on(self.inner.upload_directory_if_needed(directory))
+    }
+}
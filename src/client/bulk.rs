@@ -0,0 +1,167 @@
+use futures::{StreamExt, stream};
+use tracing::debug;
+
+use crate::{
+    client::jobs::JobSubmissionAPI,
+    schemas::{jobs::JobStatus, query::JobQuery},
+};
+
+/// Selects the jobs targeted by a bulk operation.
+#[derive(Debug, Clone)]
+pub enum JobSelector {
+    /// Jobs with the given submission IDs
+    Ids(Vec<String>),
+    /// Jobs matching a query. Jobs without a submission ID are ignored.
+    Query(JobQuery),
+}
+
+impl From<Vec<String>> for JobSelector {
+    fn from(ids: Vec<String>) -> Self {
+        JobSelector::Ids(ids)
+    }
+}
+
+impl From<JobQuery> for JobSelector {
+    fn from(query: JobQuery) -> Self {
+        JobSelector::Query(query)
+    }
+}
+
+/// Result of a bulk operation for a single job.
+#[derive(Debug)]
+pub enum BulkJobOutcome {
+    Stopped,
+    Deleted,
+    /// The job was not in a state the operation applies to
+    Skipped {
+        status: JobStatus,
+    },
+    Failed(crate::Error),
+}
+
+#[derive(Debug)]
+pub struct BulkJobResult {
+    pub submission_id: String,
+    pub outcome: BulkJobOutcome,
+}
+
+/// Per-job results of a bulk operation, in the order the jobs were selected.
+#[derive(Debug, Default)]
+pub struct BulkJobReport {
+    pub results: Vec<BulkJobResult>,
+}
+
+impl BulkJobReport {
+    /// Results for jobs the operation was applied to.
+    pub fn succeeded(&self) -> impl Iterator<Item = &BulkJobResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, BulkJobOutcome::Stopped | BulkJobOutcome::Deleted))
+    }
+
+    /// Results for jobs that were skipped.
+    pub fn skipped(&self) -> impl Iterator<Item = &BulkJobResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, BulkJobOutcome::Skipped { .. }))
+    }
+
+    /// Results for jobs the operation failed for.
+    pub fn failed(&self) -> impl Iterator<Item = &BulkJobResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, BulkJobOutcome::Failed(_)))
+    }
+
+    /// Whether the operation did not fail for any job.
+    pub fn is_success(&self) -> bool {
+        self.failed().next().is_none()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum BulkOperation {
+    Stop,
+    Delete,
+}
+
+/// Apply a bulk operation to the selected jobs with bounded concurrency.
+pub(crate) async fn run_bulk_operation<C>(
+    client: &C,
+    selector: &JobSelector,
+    operation: BulkOperation,
+    concurrency: usize,
+) -> crate::Result<BulkJobReport>
+where
+    C: JobSubmissionAPI + Sync + ?Sized,
+{
+    // Jobs selected by a query already have a known status,
+    // jobs selected by ID are looked up individually
+    let targets: Vec<(String, Option<JobStatus>)> = match selector {
+        JobSelector::Ids(ids) => ids.iter().map(|id| (id.clone(), None)).collect(),
+        JobSelector::Query(query) => client
+            .query_jobs(query)
+            .await?
+            .into_iter()
+            .filter_map(|job| job.submission_id.map(|id| (id, Some(job.status))))
+            .collect(),
+    };
+
+    debug!(
+        "Applying {:?} to {} job(s) with concurrency {}",
+        operation,
+        targets.len(),
+        concurrency
+    );
+
+    let results = stream::iter(targets)
+        .map(|(submission_id, status)| async move {
+            let outcome = apply_operation(client, &submission_id, status, operation).await;
+            BulkJobResult {
+                submission_id,
+                outcome,
+            }
+        })
+        .buffered(concurrency.max(1))
+        .collect()
+        .await;
+
+    Ok(BulkJobReport { results })
+}
+
+async fn apply_operation<C>(
+    client: &C,
+    submission_id: &str,
+    status: Option<JobStatus>,
+    operation: BulkOperation,
+) -> BulkJobOutcome
+where
+    C: JobSubmissionAPI + Sync + ?Sized,
+{
+    let status = match status {
+        Some(status) => status,
+        None => match client.get_job_status(submission_id).await {
+            Ok(status) => status,
+            Err(e) => return BulkJobOutcome::Failed(e),
+        },
+    };
+
+    match operation {
+        BulkOperation::Stop if status.is_terminal() => BulkJobOutcome::Skipped { status },
+        BulkOperation::Stop => match client.stop_job(submission_id).await {
+            Ok(response) if response.stopped => BulkJobOutcome::Stopped,
+            // The job finished between the status check and the stop request
+            Ok(_) => BulkJobOutcome::Skipped { status },
+            Err(e) => BulkJobOutcome::Failed(e),
+        },
+        BulkOperation::Delete if !status.is_terminal() => BulkJobOutcome::Skipped { status },
+        BulkOperation::Delete => match client.delete_job(submission_id).await {
+            Ok(response) if response.deleted => BulkJobOutcome::Deleted,
+            Ok(_) => BulkJobOutcome::Failed(crate::Error::Generic(format!(
+                "Job {} was not deleted",
+                submission_id
+            ))),
+            Err(e) => BulkJobOutcome::Failed(e),
+        },
+    }
+}
@@ -0,0 +1,41 @@
+use async_trait::async_trait;
+use tracing::instrument;
+
+use crate::{
+    RayDashboardClient,
+    schemas::{
+        cluster::{ClusterStatus, ClusterStatusData},
+        common::DashboardResponse,
+        jobs::JobSubmitRequest,
+    },
+};
+
+#[async_trait]
+pub trait ClusterAPI: Send + Sync {
+    /// Get the autoscaler status and resource usage of the cluster
+    async fn get_cluster_status(&self) -> crate::Result<ClusterStatus>;
+
+    /// Check if the cluster currently has enough available resources for the job's entrypoint
+    async fn can_schedule(&self, payload: &JobSubmitRequest) -> crate::Result<bool> {
+        let status = self.get_cluster_status().await?;
+        Ok(status.resources.can_fit(payload))
+    }
+}
+
+#[async_trait]
+impl ClusterAPI for RayDashboardClient {
+    #[instrument(skip_all)]
+    async fn get_cluster_status(&self) -> crate::Result<ClusterStatus> {
+        let path = "/api/cluster_status";
+        let request = self.base_request(reqwest::Method::GET, path)?;
+        let response = self.send(request).await?.error_for_status()?;
+        let response = response.json::<DashboardResponse<ClusterStatusData>>().await?;
+        if !response.result {
+            return Err(crate::Error::Generic(format!(
+                "Failed to get cluster status: {}",
+                response.msg
+            )));
+        }
+        Ok(response.data.into())
+    }
+}
@@ -0,0 +1,64 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use tracing::{debug, warn};
+
+use crate::{RayDashboardClient, client::jobs::JobSubmissionAPI};
+
+/// Stops a submitted job when dropped, unless disarmed first.
+///
+/// Used to tie the lifetime of a remote job to a local future so that cancelling the
+/// future (e.g. aborting the task awaiting it) does not leave the job running on the cluster.
+/// The stop request is spawned onto the current Tokio runtime, if there is one.
+pub(crate) struct StopOnDrop {
+    client: RayDashboardClient,
+    submission_id: String,
+    armed: AtomicBool,
+}
+
+impl StopOnDrop {
+    pub(crate) fn new(client: RayDashboardClient, submission_id: impl Into<String>, armed: bool) -> Self {
+        Self {
+            client,
+            submission_id: submission_id.into(),
+            armed: AtomicBool::new(armed),
+        }
+    }
+
+    /// Set whether the job is stopped when the guard is dropped.
+    pub(crate) fn set_armed(&self, armed: bool) {
+        self.armed.store(armed, Ordering::SeqCst);
+    }
+
+    /// Prevent the job from being stopped when the guard is dropped.
+    pub(crate) fn disarm(&self) {
+        self.set_armed(false);
+    }
+}
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        if !self.armed.load(Ordering::SeqCst) {
+            return;
+        }
+
+        let client = self.client.clone();
+        let submission_id = std::mem::take(&mut self.submission_id);
+
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                debug!("Stopping job {} after cancellation", submission_id);
+                handle.spawn(async move {
+                    if let Err(e) = client.stop_job(&submission_id).await {
+                        warn!("Failed to stop job {} after cancellation: {}", submission_id, e);
+                    }
+                });
+            }
+            Err(_) => {
+                warn!(
+                    "No Tokio runtime available to stop job {} after cancellation",
+                    submission_id
+                );
+            }
+        }
+    }
+}
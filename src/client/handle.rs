@@ -0,0 +1,211 @@
+use std::{
+    pin::pin,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+};
+
+use futures::future::{Either, select};
+use tokio::task::JoinHandle;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, warn};
+
+use crate::{
+    RayDashboardClient,
+    client::{
+        guard::StopOnDrop,
+        jobs::{JobSubmissionAPI, WaitOptions},
+    },
+    schemas::jobs::{JobDetails, JobLogsResponse, JobStatus, JobStopResponse, JobSubmitRequest},
+};
+
+/// Handle to a submitted job that can tie the lifetime of the remote job to the caller.
+///
+/// By default dropping the handle leaves the job running. Use [`JobHandle::with_stop_on_drop`]
+/// to stop the job when the handle is dropped before the job finishes, and
+/// [`JobHandle::with_cancellation_token`] to stop it when a token is cancelled.
+pub struct JobHandle {
+    client: RayDashboardClient,
+    submission_id: String,
+    guard: StopOnDrop,
+    /// Set once a stop was sent on cancellation, so it is not sent again
+    stop_sent: Arc<AtomicBool>,
+    cancellation: Option<CancellationToken>,
+    cancellation_task: Option<JoinHandle<()>>,
+}
+
+impl std::fmt::Debug for JobHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JobHandle")
+            .field("submission_id", &self.submission_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl JobHandle {
+    /// Create a handle for an already submitted job.
+    pub fn new(client: RayDashboardClient, submission_id: impl Into<String>) -> Self {
+        let submission_id = submission_id.into();
+        let guard = StopOnDrop::new(client.clone(), &submission_id, false);
+        Self {
+            client,
+            submission_id,
+            guard,
+            stop_sent: Arc::new(AtomicBool::new(false)),
+            cancellation: None,
+            cancellation_task: None,
+        }
+    }
+
+    /// Set whether to stop the job when the handle is dropped before the job finishes.
+    pub fn with_stop_on_drop(self, stop_on_drop: bool) -> Self {
+        self.guard.set_armed(stop_on_drop);
+        self
+    }
+
+    /// Stop the job when the provided token is cancelled.
+    ///
+    /// A background task waiting on the token is spawned onto the current Tokio runtime,
+    /// and [`JobHandle::wait`] returns [`crate::Error::Cancelled`] once the token fires.
+    /// The token is only observed while the handle is alive.
+    pub fn with_cancellation_token(mut self, token: CancellationToken) -> Self {
+        self.abort_cancellation_task();
+
+        match tokio::runtime::Handle::try_current() {
+            Ok(handle) => {
+                let client = self.client.clone();
+                let submission_id = self.submission_id.clone();
+                let cancelled = token.clone();
+                let stop_sent = self.stop_sent.clone();
+                self.cancellation_task = Some(handle.spawn(async move {
+                    cancelled.cancelled().await;
+                    if stop_sent.swap(true, Ordering::SeqCst) {
+                        return;
+                    }
+                    debug!("Cancellation requested, stopping job {}", submission_id);
+                    if let Err(e) = client.stop_job(&submission_id).await {
+                        warn!("Failed to stop job {} after cancellation: {}", submission_id, e);
+                    }
+                }));
+            }
+            Err(_) => {
+                warn!(
+                    "No Tokio runtime available, job {} will only be stopped on cancellation while waiting",
+                    self.submission_id
+                );
+            }
+        }
+
+        self.cancellation = Some(token);
+        self
+    }
+
+    /// The submission ID of the job.
+    pub fn submission_id(&self) -> &str {
+        &self.submission_id
+    }
+
+    /// Get the current job details.
+    pub async fn details(&self) -> crate::Result<JobDetails> {
+        let details = self.client.get_job_details(&self.submission_id).await?;
+        if details.status.is_terminal() {
+            self.release();
+        }
+        Ok(details)
+    }
+
+    /// Get the current job status.
+    pub async fn status(&self) -> crate::Result<JobStatus> {
+        Ok(self.details().await?.status)
+    }
+
+    /// Get the logs for the job.
+    pub async fn logs(&self) -> crate::Result<JobLogsResponse> {
+        self.client.get_job_logs(&self.submission_id).await
+    }
+
+    /// Stop the job.
+    pub async fn stop(&self) -> crate::Result<JobStopResponse> {
+        let response = self.client.stop_job(&self.submission_id).await?;
+        self.release();
+        Ok(response)
+    }
+
+    /// Wait for the job to reach a terminal state and return its final details.
+    ///
+    /// If a cancellation token was provided and fires while waiting,
+    /// the job is stopped and [`crate::Error::Cancelled`] is returned.
+    pub async fn wait(&self, options: &WaitOptions) -> crate::Result<JobDetails> {
+        let wait = self
+            .client
+            .wait_for_terminal_with_options(&self.submission_id, options);
+
+        let details = match self.cancellation {
+            Some(ref token) => match select(pin!(wait), pin!(token.cancelled())).await {
+                Either::Left((result, _)) => result?,
+                Either::Right(_) => {
+                    // The cancellation task may have sent the stop already, and is left to finish it
+                    if self.claim_stop() {
+                        self.release();
+                        self.client.stop_job(&self.submission_id).await?;
+                    } else {
+                        self.guard.disarm();
+                    }
+                    return Err(crate::Error::Cancelled {
+                        submission_id: self.submission_id.clone(),
+                    });
+                }
+            },
+            None => wait.await?,
+        };
+
+        self.release();
+        Ok(details)
+    }
+
+    /// Release the job from the handle so it is not stopped on drop or cancellation,
+    /// and return its submission ID.
+    pub fn detach(self) -> String {
+        self.release();
+        self.submission_id.clone()
+    }
+
+    /// Stop tracking the job once it has finished or been stopped.
+    fn release(&self) {
+        self.guard.disarm();
+        if let Some(ref task) = self.cancellation_task {
+            task.abort();
+        }
+    }
+
+    /// Record that the job is being stopped on cancellation, returning `false` if it already was.
+    fn claim_stop(&self) -> bool {
+        !self.stop_sent.swap(true, Ordering::SeqCst)
+    }
+
+    fn abort_cancellation_task(&mut self) {
+        if let Some(task) = self.cancellation_task.take() {
+            task.abort();
+        }
+    }
+}
+
+impl Drop for JobHandle {
+    fn drop(&mut self) {
+        if self.claim_stop() {
+            self.abort_cancellation_task();
+        } else {
+            // A stop was sent on cancellation, so the guard does not send another one
+            self.guard.disarm();
+        }
+    }
+}
+
+impl RayDashboardClient {
+    /// Submit a new job and return a handle that owns its lifetime.
+    pub async fn submit_job_handle(&self, payload: &JobSubmitRequest) -> crate::Result<JobHandle> {
+        let response = self.submit_job(payload).await?;
+        Ok(JobHandle::new(self.clone(), response.submission_id))
+    }
+}
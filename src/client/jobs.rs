@@ -1,31 +1,275 @@
-use std::time::{Duration, Instant};
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use futures_timer::Delay;
-use tracing::debug;
+use tracing::{Span, debug, field, instrument, warn};
 
 use crate::{
     RayDashboardClient,
-    client::packages::PackagesAPI,
+    client::{
+        bulk::{BulkJobReport, BulkOperation, JobSelector, run_bulk_operation},
+        guard::StopOnDrop,
+        metrics::SubmissionOutcome,
+        packages::PackagesAPI,
+        retry::{JobRetryPolicy, RetryReport, run_with_retry},
+        watch::{self, JobFilter, JobStatusEvent, JobsEvent},
+    },
+    constants::{
+        JOB_POLL_INTERVAL_MS, JOB_POLL_MAX_INTERVAL_MS, JOB_STOP_GRACE_PERIOD_SECS, STATE_API_MAX_LIMIT,
+    },
     schemas::jobs::{
         JobDeleteResponse, JobDetails, JobLogsResponse, JobStatus, JobStopResponse, JobSubmitRequest,
         JobSubmitResponse,
     },
+    schemas::{
+        capabilities::ServerFeature,
+        common::{DashboardResponse, StateListData},
+        query::JobQuery,
+    },
 };
 
+/// Callback invoked with the job details on every poll.
+pub type PollCallback = Arc<dyn Fn(&JobDetails) + Send + Sync>;
+
+/// Callback invoked with the previous status and the new job details when the status changes.
+pub type StatusChangeCallback = Arc<dyn Fn(Option<JobStatus>, &JobDetails) + Send + Sync>;
+
+/// Options for [`JobSubmissionAPI::wait_for_terminal_with_options`].
+#[derive(Clone)]
+pub struct WaitOptions {
+    /// Interval before the first re-poll
+    pub initial_interval: Duration,
+    /// Upper bound for the interval between polls
+    pub max_interval: Duration,
+    /// Factor the interval is multiplied by after every poll
+    pub backoff_factor: f64,
+    /// Maximum time to wait for the job to reach a terminal state
+    pub timeout: Option<Duration>,
+    on_poll: Option<PollCallback>,
+    on_status_change: Option<StatusChangeCallback>,
+}
+
+impl Default for WaitOptions {
+    fn default() -> Self {
+        Self {
+            initial_interval: Duration::from_millis(JOB_POLL_INTERVAL_MS),
+            max_interval: Duration::from_millis(JOB_POLL_MAX_INTERVAL_MS),
+            backoff_factor: 1.5,
+            timeout: None,
+            on_poll: None,
+            on_status_change: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for WaitOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WaitOptions")
+            .field("initial_interval", &self.initial_interval)
+            .field("max_interval", &self.max_interval)
+            .field("backoff_factor", &self.backoff_factor)
+            .field("timeout", &self.timeout)
+            .field("on_poll", &self.on_poll.is_some())
+            .field("on_status_change", &self.on_status_change.is_some())
+            .finish()
+    }
+}
+
+impl WaitOptions {
+    /// Create a new WaitOptions with default settings.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the interval before the first re-poll.
+    pub fn with_initial_interval(mut self, interval: Duration) -> Self {
+        self.initial_interval = interval;
+        self
+    }
+
+    /// Set the upper bound for the interval between polls.
+    pub fn with_max_interval(mut self, interval: Duration) -> Self {
+        self.max_interval = interval;
+        self
+    }
+
+    /// Set the factor the interval is multiplied by after every poll.
+    /// A factor of `1.0` polls at a fixed interval.
+    pub fn with_backoff_factor(mut self, factor: f64) -> Self {
+        self.backoff_factor = factor;
+        self
+    }
+
+    /// Set the maximum time to wait for the job to reach a terminal state.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Set a callback invoked with the job details on every poll.
+    pub fn with_on_poll(mut self, callback: impl Fn(&JobDetails) + Send + Sync + 'static) -> Self {
+        self.on_poll = Some(Arc::new(callback));
+        self
+    }
+
+    /// Set a callback invoked when the job status changes, including the first observed status.
+    pub fn with_on_status_change(
+        mut self,
+        callback: impl Fn(Option<JobStatus>, &JobDetails) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_status_change = Some(Arc::new(callback));
+        self
+    }
+
+    /// Compute the interval to use after a poll that waited `current`.
+    /// Intervals too large to represent are clamped to the maximum interval.
+    fn next_interval(&self, current: Duration) -> Duration {
+        Duration::try_from_secs_f64(current.as_secs_f64() * self.backoff_factor.max(1.0))
+            .unwrap_or(self.max_interval)
+            .min(self.max_interval)
+    }
+}
+
+/// Options for [`JobSubmissionAPI::run_job`].
+#[derive(Debug, Clone)]
+pub struct RunOptions {
+    /// How to wait for the job to finish, including the timeout
+    pub wait: WaitOptions,
+    /// Stop the job if it does not finish within the timeout
+    pub stop_on_timeout: bool,
+    /// Stop the job if the `run_job` future is dropped before it completes
+    pub stop_on_cancel: bool,
+    /// Fetch the job logs once it has finished
+    pub fetch_logs: bool,
+}
+
+impl Default for RunOptions {
+    fn default() -> Self {
+        Self {
+            wait: WaitOptions::default(),
+            stop_on_timeout: true,
+            stop_on_cancel: false,
+            fetch_logs: true,
+        }
+    }
+}
+
+impl RunOptions {
+    /// Create a new RunOptions with default settings.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the maximum time to wait for the job to finish.
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.wait.timeout = Some(timeout);
+        self
+    }
+
+    /// Set the options used to wait for the job to finish.
+    pub fn with_wait_options(mut self, wait: WaitOptions) -> Self {
+        self.wait = wait;
+        self
+    }
+
+    /// Set whether to stop the job when the timeout elapses.
+    pub fn with_stop_on_timeout(mut self, stop: bool) -> Self {
+        self.stop_on_timeout = stop;
+        self
+    }
+
+    /// Set whether to stop the job when the `run_job` future is cancelled.
+    pub fn with_stop_on_cancel(mut self, stop: bool) -> Self {
+        self.stop_on_cancel = stop;
+        self
+    }
+
+    /// Set whether to fetch the job logs once it has finished.
+    pub fn with_fetch_logs(mut self, fetch_logs: bool) -> Self {
+        self.fetch_logs = fetch_logs;
+        self
+    }
+}
+
+/// Final state of a job run with [`JobSubmissionAPI::run_job`].
+#[derive(Debug, Clone)]
+pub struct JobOutcome {
+    pub submission_id: String,
+    pub details: JobDetails,
+    pub logs: Option<String>,
+    /// Whether the job did not finish within the configured timeout
+    pub timed_out: bool,
+}
+
+impl JobOutcome {
+    /// Status of the job when the outcome was collected.
+    pub fn status(&self) -> JobStatus {
+        self.details.status
+    }
+
+    /// Exit code of the job driver, if it has exited.
+    pub fn exit_code(&self) -> Option<i32> {
+        self.details.driver_exit_code
+    }
+
+    /// Whether the job finished successfully.
+    pub fn is_success(&self) -> bool {
+        !self.timed_out && self.details.status == JobStatus::SUCCEEDED
+    }
+
+    /// Convert the outcome into an error if the job did not succeed.
+    pub fn into_result(self) -> crate::Result<Self> {
+        if self.is_success() {
+            return Ok(self);
+        }
+
+        if self.timed_out {
+            return Err(crate::Error::Timeout {
+                submission_id: self.submission_id,
+                last_status: self.details.status,
+            });
+        }
+
+        Err(crate::Error::JobFailed {
+            submission_id: self.submission_id,
+            status: self.details.status,
+            message: self.details.message,
+        })
+    }
+}
+
+/// Job submission API of the Ray dashboard.
+///
+/// Implementors provide the endpoint methods; polling, bulk operations and `run_job` are
+/// built on top of them by default.
 #[async_trait]
-pub trait JobSubmissionAPI {
+pub trait JobSubmissionAPI: Send + Sync {
     /// Submit a new job
     async fn submit_job(&self, payload: &JobSubmitRequest) -> crate::Result<JobSubmitResponse>;
 
     /// List all jobs
     async fn list_jobs(&self) -> crate::error::Result<Vec<JobDetails>>;
 
+    /// List jobs matching a query
+    /// By default every job is listed and the query is applied client-side.
+    #[instrument(skip_all)]
+    async fn query_jobs(&self, query: &JobQuery) -> crate::Result<Vec<JobDetails>> {
+        let jobs = self.list_jobs().await?;
+        Ok(query.apply(jobs))
+    }
+
     /// Get job details by submission ID
     async fn get_job_details(&self, submission_id: &str) -> crate::Result<JobDetails>;
 
     /// Get job status by fetching details and then extracting status
-    async fn get_job_status(&self, submission_id: &str) -> crate::Result<JobStatus>;
+    async fn get_job_status(&self, submission_id: &str) -> crate::Result<JobStatus> {
+        let details = self.get_job_details(submission_id).await?;
+        Ok(details.status)
+    }
 
     /// Delete a job
     async fn delete_job(&self, submission_id: &str) -> crate::Result<JobDeleteResponse>;
@@ -33,108 +277,408 @@ pub trait JobSubmissionAPI {
     /// Stop a running job
     async fn stop_job(&self, submission_id: &str) -> crate::Result<JobStopResponse>;
 
+    /// Stop the selected jobs with bounded concurrency, skipping jobs that are already terminal
+    /// Failures are reported per job rather than aborting the whole operation.
+    #[instrument(skip(self, selector))]
+    async fn stop_jobs(&self, selector: &JobSelector, concurrency: usize) -> crate::Result<BulkJobReport> {
+        run_bulk_operation(self, selector, BulkOperation::Stop, concurrency).await
+    }
+
+    /// Delete the selected jobs with bounded concurrency, skipping jobs that are not yet terminal
+    /// Failures are reported per job rather than aborting the whole operation.
+    #[instrument(skip(self, selector))]
+    async fn delete_jobs(&self, selector: &JobSelector, concurrency: usize) -> crate::Result<BulkJobReport> {
+        run_bulk_operation(self, selector, BulkOperation::Delete, concurrency).await
+    }
+
     /// Get the logs for a job
     async fn get_job_logs(&self, submission_id: &str) -> crate::Result<JobLogsResponse>;
 
-    /// Wait for the job to reach a terminal state
-    /// Return an error if the job does not reach a terminal state within the provided max duration.
+    /// Wait for the job to reach a terminal state and return its final details
+    /// Return a timeout error if the job does not reach a terminal state within the provided max duration.
+    #[instrument(skip(self))]
     async fn wait_for_terminal(
         &self,
         submission_id: &str,
         max_duration: Option<Duration>,
-    ) -> crate::Result<()>;
-}
+    ) -> crate::Result<JobDetails> {
+        let options = WaitOptions {
+            timeout: max_duration,
+            ..Default::default()
+        };
+        self.wait_for_terminal_with_options(submission_id, &options).await
+    }
 
-#[async_trait]
-impl JobSubmissionAPI for RayDashboardClient {
-    async fn submit_job(&self, payload: &JobSubmitRequest) -> crate::Result<JobSubmitResponse> {
-        // Clone the payload so we can potentially modify the runtime_env
-        let mut payload = payload.clone();
+    /// Wait for the job to reach a terminal state with configurable polling and return its final details
+    #[instrument(skip(self, options))]
+    async fn wait_for_terminal_with_options(
+        &self,
+        submission_id: &str,
+        options: &WaitOptions,
+    ) -> crate::Result<JobDetails> {
+        let start = Instant::now();
+        let mut interval = options.initial_interval;
+        let mut last_status = None;
+
+        loop {
+            let details = self.get_job_details(submission_id).await?;
 
-        // Check if there's a runtime_env with a working_dir that needs to be uploaded
-        if let Some(ref mut runtime_env) = payload.runtime_env
-            && let Some(ref working_dir_path) = runtime_env.working_dir
-        {
-            debug!("Uploading working directory: {:?}", working_dir_path);
+            if let Some(ref on_poll) = options.on_poll {
+                on_poll(&details);
+            }
+            if last_status != Some(details.status) {
+                if let Some(ref on_status_change) = options.on_status_change {
+                    on_status_change(last_status, &details);
+                }
+                last_status = Some(details.status);
+            }
 
-            // Upload the directory and get the URI
-            let package_uri = self.upload_directory_if_needed(working_dir_path).await?;
+            if details.status.is_terminal() {
+                return Ok(details);
+            }
 
-            debug!("Working directory uploaded, URI: {}", package_uri);
+            let mut delay = interval;
+            if let Some(timeout) = options.timeout {
+                let elapsed = start.elapsed();
+                if elapsed >= timeout {
+                    return Err(crate::Error::Timeout {
+                        submission_id: submission_id.to_string(),
+                        last_status: details.status,
+                    });
+                }
+                // Do not sleep past the deadline
+                delay = delay.min(timeout - elapsed);
+            }
 
-            // Update the runtime_env with the package URI string
-            runtime_env.working_dir = Some(package_uri.into());
+            Delay::new(delay).await;
+            interval = options.next_interval(interval);
         }
+    }
 
-        let path = "/api/jobs/";
-        let request = self.base_request(reqwest::Method::POST, path)?;
-        let response = request.json(&payload).send().await?.error_for_status()?;
-        Ok(response.json::<JobSubmitResponse>().await?)
+    /// Watch a single job, emitting an event whenever its status or message changes.
+    ///
+    /// The first event reports the status at the time of the first poll.
+    /// The stream ends after the job reaches a terminal state. Errors are yielded
+    /// without ending the stream, so callers can decide whether to keep watching,
+    /// except for a `404 Not Found`, which ends the stream after it is yielded.
+    fn watch_job(
+        &self,
+        submission_id: &str,
+        poll_interval: Duration,
+    ) -> BoxStream<'_, crate::Result<JobStatusEvent>> {
+        watch::watch_job(self, submission_id, poll_interval)
+    }
+
+    /// Watch all jobs matching a filter by diffing successive job listings.
+    ///
+    /// The first poll emits a [`JobsEvent::Created`] event for every matching job. Created and
+    /// updated jobs are reported in listing order, followed by removed jobs sorted by ID.
+    /// The stream never ends on its own. Errors are yielded without ending the stream.
+    fn watch_jobs(
+        &self,
+        filter: JobFilter,
+        poll_interval: Duration,
+    ) -> BoxStream<'_, crate::Result<JobsEvent>> {
+        watch::watch_jobs(self, filter, poll_interval)
+    }
+
+    /// Submit a job, wait for it to finish, and collect its final details and logs.
+    /// Use [`JobOutcome::into_result`] to turn an unsuccessful outcome into an error.
+    /// The default implementation ignores `stop_on_cancel`.
+    #[instrument(skip_all, fields(submission_id = field::Empty))]
+    async fn run_job(&self, payload: &JobSubmitRequest, options: RunOptions) -> crate::Result<JobOutcome> {
+        let submission_id = self.submit_job(payload).await?.submission_id;
+        Span::current().record("submission_id", submission_id.as_str());
+        let (details, timed_out) = wait_for_outcome(self, &submission_id, &options).await?;
+        collect_outcome(self, submission_id, details, timed_out, &options).await
     }
 
+    /// Run a job like `run_job`, resubmitting it while the policy allows.
+    /// Retries use the requested submission ID suffixed with `-retry-<n>`, or let Ray generate one if
+    /// none was requested. The ID is taken from the request rather than the outcome, which a pool
+    /// has already prefixed with the cluster name.
+    /// Errors submitting or waiting for a job are not retried and end the run.
+    #[instrument(skip_all)]
+    async fn run_job_with_retry(
+        &self,
+        payload: &JobSubmitRequest,
+        options: RunOptions,
+        policy: &JobRetryPolicy,
+    ) -> RetryReport {
+        run_with_retry(self, payload, options, policy).await
+    }
+}
+
+#[async_trait]
+impl JobSubmissionAPI for RayDashboardClient {
+    #[instrument(skip_all, fields(submission_id = payload.submission_id.as_deref()))]
+    async fn submit_job(&self, payload: &JobSubmitRequest) -> crate::Result<JobSubmitResponse> {
+        let result = self.prepare_and_submit_job(payload).await;
+        if let Some(ref recorder) = self.metrics {
+            recorder.record_job_submission(SubmissionOutcome::from_result(&result));
+        }
+        result
+    }
+
+    #[instrument(skip_all)]
     async fn list_jobs(&self) -> crate::error::Result<Vec<JobDetails>> {
         let path = "/api/jobs/";
         let request = self.base_request(reqwest::Method::GET, path)?;
-        let response = request.send().await?.error_for_status()?;
+        let response = self.send(request).await?.error_for_status()?;
         Ok(response.json::<Vec<JobDetails>>().await?)
     }
 
+    /// Servers with the state API filter by status and job type and apply the limit where
+    /// possible; the rest of the query is applied client-side. Other servers list every job.
+    #[instrument(skip_all)]
+    async fn query_jobs(&self, query: &JobQuery) -> crate::Result<Vec<JobDetails>> {
+        let state_api = match self.server_capabilities().await {
+            Ok(capabilities) => capabilities.supports(ServerFeature::StateApi),
+            Err(e) => {
+                debug!("Failed to get server capabilities, listing all jobs: {}", e);
+                false
+            }
+        };
+        if state_api {
+            match self.query_jobs_state_api(query).await {
+                Ok(Some(jobs)) => return Ok(query.apply(jobs)),
+                Ok(None) => debug!("State API truncated the job list, listing all jobs"),
+                Err(e) => debug!(
+                    "Failed to query jobs through the state API, listing all jobs: {}",
+                    e
+                ),
+            }
+        }
+        let jobs = self.list_jobs().await?;
+        Ok(query.apply(jobs))
+    }
+
+    #[instrument(skip(self))]
     async fn get_job_details(&self, submission_id: &str) -> crate::Result<JobDetails> {
         let path = format!("/api/jobs/{}", submission_id);
         let request = self.base_request(reqwest::Method::GET, &path)?;
-        let response = request.send().await?.error_for_status()?;
+        let response = self.send(request).await?.error_for_status()?;
         Ok(response.json::<JobDetails>().await?)
     }
 
-    async fn get_job_status(&self, submission_id: &str) -> crate::Result<JobStatus> {
-        let details = self.get_job_details(submission_id).await?;
-        Ok(details.status)
-    }
-
+    #[instrument(skip(self))]
     async fn delete_job(&self, submission_id: &str) -> crate::Result<JobDeleteResponse> {
         let path = format!("/api/jobs/{}", submission_id);
         let request = self.base_request(reqwest::Method::DELETE, &path)?;
-        let response = request.send().await?.error_for_status()?;
+        let response = self.send(request).await?.error_for_status()?;
         Ok(response.json::<JobDeleteResponse>().await?)
     }
 
+    #[instrument(skip(self))]
     async fn stop_job(&self, submission_id: &str) -> crate::Result<JobStopResponse> {
         let path = format!("/api/jobs/{}/stop", submission_id);
         let request = self.base_request(reqwest::Method::POST, &path)?;
-        let response = request.send().await?.error_for_status()?;
+        let response = self.send(request).await?.error_for_status()?;
         Ok(response.json::<JobStopResponse>().await?)
     }
 
+    #[instrument(skip(self))]
     async fn get_job_logs(&self, submission_id: &str) -> crate::Result<JobLogsResponse> {
         let path = format!("/api/jobs/{}/logs", submission_id);
         let request = self.base_request(reqwest::Method::GET, &path)?;
-        let response = request.send().await?.error_for_status()?;
+        let response = self.send(request).await?.error_for_status()?;
         Ok(response.json::<JobLogsResponse>().await?)
     }
 
-    async fn wait_for_terminal(
+    #[instrument(skip_all, fields(submission_id = field::Empty))]
+    async fn run_job(&self, payload: &JobSubmitRequest, options: RunOptions) -> crate::Result<JobOutcome> {
+        let submission_id = self.submit_job(payload).await?.submission_id;
+        Span::current().record("submission_id", submission_id.as_str());
+        let guard = StopOnDrop::new(self.clone(), &submission_id, options.stop_on_cancel);
+        let result = wait_for_outcome(self, &submission_id, &options).await;
+        // Only stop the job if this future is dropped, not if waiting failed
+        guard.disarm();
+        let (details, timed_out) = result?;
+        let outcome = collect_outcome(self, submission_id, details, timed_out, &options).await?;
+        if let Some(ref recorder) = self.metrics {
+            recorder.record_job_outcome(&outcome);
+        }
+        Ok(outcome)
+    }
+
+    #[instrument(skip_all)]
+    async fn run_job_with_retry(
         &self,
-        submission_id: &str,
-        max_duration: Option<Duration>,
-    ) -> crate::Result<()> {
-        let start = Instant::now();
+        payload: &JobSubmitRequest,
+        options: RunOptions,
+        policy: &JobRetryPolicy,
+    ) -> RetryReport {
+        let report = run_with_retry(self, payload, options, policy).await;
+        if let Some(ref recorder) = self.metrics {
+            recorder.record_job_retries(&report);
+        }
+        report
+    }
+}
 
-        loop {
-            let status = self.get_job_status(submission_id).await?;
-            if status.is_terminal() {
-                return Ok(());
+impl RayDashboardClient {
+    /// List jobs through the state API with the filters and limit it supports.
+    /// Returns `None` if the server truncated the jobs before filtering them, or returned fewer jobs than
+    /// requested while more matched, since either can drop matching jobs.
+    async fn query_jobs_state_api(&self, query: &JobQuery) -> crate::Result<Option<Vec<JobDetails>>> {
+        let limit = query.state_api_limit();
+        let mut params = vec![
+            ("limit", limit.unwrap_or(STATE_API_MAX_LIMIT).to_string()),
+            ("detail", "true".to_string()),
+        ];
+        for (key, predicate, value) in query.state_api_filters() {
+            params.push(("filter_keys", key.to_string()));
+            params.push(("filter_predicates", predicate.to_string()));
+            params.push(("filter_values", value));
+        }
+
+        let path = "/api/v0/jobs";
+        let request = self.base_request(reqwest::Method::GET, path)?.query(&params);
+        let response = self.send(request).await?.error_for_status()?;
+        let response = response
+            .json::<DashboardResponse<StateListData<JobDetails>>>()
+            .await?;
+        if !response.result {
+            return Err(crate::Error::Generic(format!(
+                "Failed to list jobs: {}",
+                response.msg
+            )));
+        }
+
+        let result = response.data.result;
+        let short = limit.is_none_or(|limit| result.result.len() < limit);
+        if result.is_truncated() || (short && result.is_limited()) {
+            return Ok(None);
+        }
+        Ok(Some(result.result))
+    }
+
+    /// Upload the working directory if needed and submit the job.
+    async fn prepare_and_submit_job(&self, payload: &JobSubmitRequest) -> crate::Result<JobSubmitResponse> {
+        if payload.runtime_env.as_ref().is_some_and(|env| env.uv.is_some()) {
+            self.require_feature(ServerFeature::UvRuntimeEnv).await?;
+        }
+
+        let payload = self.resolve_working_dir(payload).await?;
+
+        let path = "/api/jobs/";
+        let request = self.base_request(reqwest::Method::POST, path)?;
+        let response = self.send(request.json(&payload)).await?.error_for_status()?;
+        let response = response.json::<JobSubmitResponse>().await?;
+        Span::current().record("submission_id", response.submission_id.as_str());
+
+        #[cfg(feature = "registry")]
+        if let Some(ref registry) = self.registry {
+            let entry = crate::registry::RegistryEntry::new(
+                self.base_url.as_str(),
+                &response.submission_id,
+                &payload,
+            );
+            if let Err(e) = registry.record(entry).await {
+                tracing::warn!(
+                    "Failed to record job {} in the registry: {}",
+                    response.submission_id,
+                    e
+                );
             }
+        }
+        Ok(response)
+    }
+}
 
-            if let Some(max_duration) = max_duration
-                && start.elapsed() >= max_duration
-            {
-                return Err(crate::Error::Generic(format!(
-                    "Job {} did not reach terminal state within {:?}",
-                    submission_id, max_duration
-                )));
+/// Wait for a submitted job, stopping it on timeout if requested.
+/// Returns the final details and whether the wait timed out.
+async fn wait_for_outcome<C>(
+    client: &C,
+    submission_id: &str,
+    options: &RunOptions,
+) -> crate::Result<(JobDetails, bool)>
+where
+    C: JobSubmissionAPI + ?Sized,
+{
+    match client
+        .wait_for_terminal_with_options(submission_id, &options.wait)
+        .await
+    {
+        Ok(details) => Ok((details, false)),
+        Err(crate::Error::Timeout { .. }) if options.stop_on_timeout => {
+            debug!("Job {} timed out, stopping it", submission_id);
+            if let Err(e) = client.stop_job(submission_id).await {
+                warn!("Failed to stop job {} after it timed out: {}", submission_id, e);
+                return Ok((client.get_job_details(submission_id).await?, true));
             }
 
-            Delay::new(Duration::from_millis(500)).await;
+            let grace_period = Duration::from_secs(JOB_STOP_GRACE_PERIOD_SECS);
+            let details = match client.wait_for_terminal(submission_id, Some(grace_period)).await {
+                Ok(details) => details,
+                Err(crate::Error::Timeout { .. }) => client.get_job_details(submission_id).await?,
+                Err(e) => return Err(e),
+            };
+            Ok((details, true))
         }
+        Err(crate::Error::Timeout { .. }) => Ok((client.get_job_details(submission_id).await?, true)),
+        Err(e) => Err(e),
+    }
+}
+
+async fn collect_outcome<C>(
+    client: &C,
+    submission_id: String,
+    details: JobDetails,
+    timed_out: bool,
+    options: &RunOptions,
+) -> crate::Result<JobOutcome>
+where
+    C: JobSubmissionAPI + ?Sized,
+{
+    let logs = if options.fetch_logs {
+        Some(client.get_job_logs(&submission_id).await?.logs)
+    } else {
+        None
+    };
+
+    Ok(JobOutcome {
+        submission_id,
+        details,
+        logs,
+        timed_out,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wait_options_backoff() {
+        let options = WaitOptions::new()
+            .with_initial_interval(Duration::from_millis(100))
+            .with_max_interval(Duration::from_millis(300))
+            .with_backoff_factor(2.0);
+
+        let first = options.next_interval(options.initial_interval);
+        assert_eq!(first, Duration::from_millis(200));
+        assert_eq!(options.next_interval(first), Duration::from_millis(300));
+    }
+
+    #[test]
+    fn test_wait_options_huge_backoff_factor() {
+        let options = WaitOptions::new()
+            .with_max_interval(Duration::from_secs(10))
+            .with_backoff_factor(f64::INFINITY);
+        assert_eq!(
+            options.next_interval(options.initial_interval),
+            Duration::from_secs(10)
+        );
+
+        let options = options.with_backoff_factor(f64::MAX);
+        assert_eq!(options.next_interval(Duration::MAX), Duration::from_secs(10));
+    }
+
+    #[test]
+    fn test_wait_options_fixed_interval() {
+        let options = WaitOptions::new().with_backoff_factor(1.0);
+        let interval = options.next_interval(options.initial_interval);
+        assert_eq!(interval, options.initial_interval);
     }
 }
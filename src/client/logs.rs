@@ -0,0 +1,356 @@
+use async_trait::async_trait;
+use futures::{
+    StreamExt, TryStreamExt,
+    stream::{self, BoxStream},
+};
+use tracing::instrument;
+
+use crate::{
+    RayDashboardClient,
+    schemas::{
+        capabilities::ServerFeature,
+        common::DashboardResponse,
+        jobs::{JobDetails, JobType},
+        logs::{LogListResult, LogListing, LogQuery},
+    },
+};
+
+#[async_trait]
+pub trait LogsAPI: Send + Sync {
+    /// List the log files on a node, optionally filtered by a glob pattern
+    async fn list_logs(&self, node_id: &str, glob: Option<&str>) -> crate::Result<LogListing>;
+
+    /// Get the contents of a log file
+    async fn get_log(&self, query: &LogQuery) -> crate::Result<String>;
+
+    /// Follow a log file, yielding new output as it is written
+    async fn follow_log(&self, query: &LogQuery) -> crate::Result<BoxStream<'static, crate::Result<String>>>;
+
+    /// Get the driver logs for a job, locating them from the job details
+    /// Works for both `SUBMISSION` and `DRIVER` jobs.
+    async fn get_driver_logs(&self, job: &JobDetails, lines: Option<i64>) -> crate::Result<String> {
+        let mut query = driver_log_query(job)?;
+        query.lines = lines;
+        self.get_log(&query).await
+    }
+}
+
+#[async_trait]
+impl LogsAPI for RayDashboardClient {
+    #[instrument(skip(self))]
+    async fn list_logs(&self, node_id: &str, glob: Option<&str>) -> crate::Result<LogListing> {
+        self.require_feature(ServerFeature::StateApi).await?;
+
+        let path = "/api/v0/logs";
+        let mut params = vec![("node_id", node_id)];
+        if let Some(glob) = glob {
+            params.push(("glob", glob));
+        }
+
+        let request = self.base_request(reqwest::Method::GET, path)?.query(&params);
+        let response = self.send(request).await?.error_for_status()?;
+        let response = response.json::<DashboardResponse<LogListResult>>().await?;
+        if !response.result {
+            return Err(crate::Error::Generic(format!(
+                "Failed to list logs: {}",
+                response.msg
+            )));
+        }
+        Ok(response.data.into())
+    }
+
+    #[instrument(skip_all, fields(submission_id = query.submission_id.as_deref()))]
+    async fn get_log(&self, query: &LogQuery) -> crate::Result<String> {
+        self.require_feature(ServerFeature::StateApi).await?;
+
+        let path = "/api/v0/logs/file";
+        let request = self
+            .base_request(reqwest::Method::GET, path)?
+            .query(&query.to_params());
+        let response = self.send(request).await?.error_for_status()?;
+
+        let mut decoder = LogDecoder::file();
+        let mut chunks = response.bytes_stream();
+        let mut log = String::new();
+        while let Some(chunk) = chunks.try_next().await? {
+            log.push_str(&decoder.decode(&chunk)?);
+        }
+        log.push_str(&decoder.finish()?);
+        Ok(log)
+    }
+
+    #[instrument(skip_all, fields(submission_id = query.submission_id.as_deref()))]
+    async fn follow_log(&self, query: &LogQuery) -> crate::Result<BoxStream<'static, crate::Result<String>>> {
+        self.require_feature(ServerFeature::StateApi).await?;
+
+        let path = "/api/v0/logs/stream";
+        let request = self
+            .base_request(reqwest::Method::GET, path)?
+            .query(&query.to_params());
+        let response = self.send(request).await?.error_for_status()?;
+
+        let state = (response.bytes_stream().boxed(), Some(LogDecoder::stream()));
+        let stream = stream::unfold(state, |(mut chunks, mut decoder)| async move {
+            let text = match chunks.next().await {
+                Some(Ok(chunk)) => decoder.as_mut()?.decode(&chunk),
+                Some(Err(e)) => Err(e.into()),
+                None => decoder.take()?.finish(),
+            };
+            Some((text, (chunks, decoder)))
+        })
+        .try_filter(|text| futures::future::ready(!text.is_empty()));
+        Ok(stream.boxed())
+    }
+}
+
+/// Build the query locating the driver log of a job.
+///
+/// Submission drivers write to `job-driver-<submission_id>.log` on the driver node,
+/// while drivers started outside of the jobs API are located by their process ID.
+pub fn driver_log_query(job: &JobDetails) -> crate::Result<LogQuery> {
+    match job.job_type {
+        JobType::SUBMISSION => {
+            let submission_id = job
+                .submission_id
+                .as_deref()
+                .ok_or_else(|| crate::Error::Generic("Submission job has no submission ID".to_string()))?;
+            match job.driver_node_id {
+                Some(ref node_id) => Ok(LogQuery::for_file(
+                    node_id,
+                    format!("job-driver-{}.log", submission_id),
+                )),
+                None => Ok(LogQuery::for_submission(submission_id)),
+            }
+        }
+        JobType::DRIVER => {
+            let driver_info = job
+                .driver_info
+                .as_ref()
+                .ok_or_else(|| crate::Error::Generic("Driver job has no driver info".to_string()))?;
+            let pid = driver_info.pid.parse::<u32>().map_err(|e| {
+                crate::Error::Generic(format!("Invalid driver pid {}: {}", driver_info.pid, e))
+            })?;
+
+            let query = match job.driver_node_id {
+                Some(ref node_id) => LogQuery::for_pid(node_id, pid),
+                None => LogQuery {
+                    pid: Some(pid),
+                    ..Default::default()
+                }
+                .with_node_ip(&driver_info.node_ip_address),
+            };
+            Ok(query)
+        }
+    }
+}
+
+/// Decoder for the body of the log endpoints.
+///
+/// Ray prefixes every write with `1`, or with `0` followed by an error message. Whether a response
+/// is framed this way is decided once from its first byte, so the text of an unframed response,
+/// e.g. from a proxy or an older dashboard, is never mistaken for a prefix.
+///
+/// In a framed response, HTTP chunks do not line up with Ray's writes, so the prefix is only read at
+/// frame starts: the start of the response, and for streams the start of a chunk following a
+/// complete line, since Ray streams whole lines. Ray's writes are sent as separate HTTP chunks, which
+/// the client may split but does not merge. Bytes of a character split across chunks are kept until
+/// the rest of it arrives.
+#[derive(Debug)]
+struct LogDecoder {
+    /// Whether a chunk following a complete line starts a new frame
+    line_frames: bool,
+    /// Whether the response is framed, or `None` before its first byte
+    framed: Option<bool>,
+    at_frame_start: bool,
+    /// Error message being received after a `0` prefix
+    error: Option<Vec<u8>>,
+    /// Trailing bytes of an incomplete UTF-8 character
+    pending: Vec<u8>,
+}
+
+impl LogDecoder {
+    /// Decoder for `/api/v0/logs/file`, whose body is a single frame.
+    fn file() -> Self {
+        Self::new(false)
+    }
+
+    /// Decoder for `/api/v0/logs/stream`, which writes a frame per batch of lines.
+    fn stream() -> Self {
+        Self::new(true)
+    }
+
+    fn new(line_frames: bool) -> Self {
+        Self {
+            line_frames,
+            framed: None,
+            at_frame_start: true,
+            error: None,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Decode a chunk, returning the complete text it contains.
+    fn decode(&mut self, chunk: &[u8]) -> crate::Result<String> {
+        if let Some(ref mut error) = self.error {
+            error.extend_from_slice(chunk);
+            return Ok(String::new());
+        }
+        let Some(&last) = chunk.last() else {
+            return Ok(String::new());
+        };
+
+        let framed = *self
+            .framed
+            .get_or_insert_with(|| matches!(chunk.first(), Some(b'0' | b'1')));
+        let mut data = chunk;
+        if framed && self.at_frame_start {
+            match data.split_first() {
+                Some((b'1', rest)) => data = rest,
+                Some((b'0', message)) => {
+                    self.error = Some(message.to_vec());
+                    return Ok(String::new());
+                }
+                _ => {}
+            }
+        }
+        self.at_frame_start = self.line_frames && last == b'\n';
+
+        self.pending.extend_from_slice(data);
+        let (text, rest) = decode_utf8(&self.pending);
+        self.pending = rest.to_vec();
+        Ok(text)
+    }
+
+    /// Finish decoding at the end of the response.
+    fn finish(self) -> crate::Result<String> {
+        if let Some(error) = self.error {
+            return Err(crate::Error::Generic(format!(
+                "Failed to get log: {}",
+                String::from_utf8_lossy(&error)
+            )));
+        }
+        Ok(String::from_utf8_lossy(&self.pending).into_owned())
+    }
+}
+
+/// Decode the complete UTF-8 text of a buffer, replacing invalid sequences.
+/// Returns the text and the trailing bytes of an incomplete character.
+fn decode_utf8(mut bytes: &[u8]) -> (String, &[u8]) {
+    let mut text = String::new();
+    loop {
+        match std::str::from_utf8(bytes) {
+            Ok(valid) => {
+                text.push_str(valid);
+                return (text, &[]);
+            }
+            Err(e) => {
+                let (valid, rest) = bytes.split_at(e.valid_up_to());
+                text.push_str(std::str::from_utf8(valid).expect("Prefix is valid UTF-8"));
+                match e.error_len() {
+                    Some(len) => {
+                        text.push(char::REPLACEMENT_CHARACTER);
+                        bytes = &rest[len..];
+                    }
+                    None => return (text, rest),
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::jobs::{JobDriverInfo, JobStatus, JobType};
+
+    fn decode(mut decoder: LogDecoder, chunks: &[&[u8]]) -> crate::Result<String> {
+        let mut log = String::new();
+        for chunk in chunks {
+            log.push_str(&decoder.decode(chunk)?);
+        }
+        log.push_str(&decoder.finish()?);
+        Ok(log)
+    }
+
+    #[test]
+    fn test_decode_log_file() {
+        assert_eq!(decode(LogDecoder::file(), &[b"1hello\n"]).unwrap(), "hello\n");
+        assert_eq!(decode(LogDecoder::file(), &[b""]).unwrap(), "");
+        assert!(decode(LogDecoder::file(), &[b"0file ", b"not found"]).is_err());
+
+        // Continuations starting with a prefix byte are data
+        let log = decode(LogDecoder::file(), &[b"1step ", b"1 of 2\n", b"0 errors\n"]).unwrap();
+        assert_eq!(log, "step 1 of 2\n0 errors\n");
+    }
+
+    #[test]
+    fn test_decode_log_stream() {
+        // A write split mid-line, then a new write after a complete line
+        let log = decode(LogDecoder::stream(), &[b"1epoch ", b"1\n", b"1epoch 2\n"]).unwrap();
+        assert_eq!(log, "epoch 1\nepoch 2\n");
+
+        // Several lines of one write coalesced into a single chunk
+        let log = decode(LogDecoder::stream(), &[b"1a\n10 items\n"]).unwrap();
+        assert_eq!(log, "a\n10 items\n");
+
+        let error = decode(LogDecoder::stream(), &[b"1a\n", b"0Closing ", b"stream"]).unwrap_err();
+        assert!(error.to_string().contains("Closing stream"));
+    }
+
+    #[test]
+    fn test_decode_log_digit_leading_lines() {
+        let chunks: &[&[u8]] = &[b"loss 0.1\n", b"1 epoch done\n", b"0.95 accuracy\n"];
+        let log = decode(LogDecoder::stream(), chunks).unwrap();
+        assert_eq!(log, "loss 0.1\n1 epoch done\n0.95 accuracy\n");
+        assert_eq!(
+            decode(LogDecoder::file(), &[b"epoch ", b"1\n"]).unwrap(),
+            "epoch 1\n"
+        );
+
+        let chunks: &[&[u8]] = &[b"1loss 0.1\n", b"11 epoch done\n", b"10.95 accuracy\n"];
+        let log = decode(LogDecoder::stream(), chunks).unwrap();
+        assert_eq!(log, "loss 0.1\n1 epoch done\n0.95 accuracy\n");
+    }
+
+    #[test]
+    fn test_decode_log_split_character() {
+        let bytes = "1naïve 日本\n".as_bytes();
+        let chunks: Vec<&[u8]> = bytes.chunks(1).collect();
+        assert_eq!(decode(LogDecoder::file(), &chunks).unwrap(), "naïve 日本\n");
+
+        assert_eq!(
+            decode(LogDecoder::file(), &[b"1a\xffb", b"\xe6"]).unwrap(),
+            "a\u{fffd}b\u{fffd}"
+        );
+    }
+
+    #[test]
+    fn test_driver_log_query_submission() {
+        let job = JobDetails {
+            driver_node_id: Some("node123".to_string()),
+            ..JobDetails::test_job("raysubmit_123", JobStatus::SUCCEEDED)
+        };
+        let query = driver_log_query(&job).unwrap();
+        assert_eq!(
+            query,
+            LogQuery::for_file("node123", "job-driver-raysubmit_123.log")
+        );
+    }
+
+    #[test]
+    fn test_driver_log_query_driver() {
+        let job = JobDetails {
+            job_type: JobType::DRIVER,
+            submission_id: None,
+            driver_info: Some(JobDriverInfo {
+                id: "01000000".to_string(),
+                node_ip_address: "10.0.0.1".to_string(),
+                pid: "4242".to_string(),
+            }),
+            ..JobDetails::test_job("", JobStatus::RUNNING)
+        };
+        let query = driver_log_query(&job).unwrap();
+        assert_eq!(query.pid, Some(4242));
+        assert_eq!(query.node_ip.as_deref(), Some("10.0.0.1"));
+    }
+}
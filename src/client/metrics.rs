@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use crate::client::{jobs::JobOutcome, retry::RetryReport};
+
+/// Receives metrics about the requests sent by a `RayDashboardClient`.
+///
+/// Implementations are called inline after each request, so they should only update counters
+/// and histograms rather than block. With the `metrics` feature, [`MetricsCrateRecorder`]
+/// forwards everything to the `metrics` crate, e.g. for a Prometheus exporter.
+pub trait MetricsRecorder: Send + Sync {
+    /// Called once for every request sent to the dashboard, including failed ones.
+    fn record_request(&self, metrics: &RequestMetrics);
+
+    /// Called once for every job submitted with `submit_job`.
+    fn record_job_submission(&self, _outcome: SubmissionOutcome) {}
+
+    /// Called once for every job run to completion with `RayDashboardClient::run_job`.
+    ///
+    /// Only the client's own `run_job` reports outcomes, so jobs run through the default
+    /// `run_job`, e.g. by `RayClusterPool` or `FakeRayDashboard`, are not recorded. With
+    /// `run_job_with_retry`, every attempt is recorded.
+    fn record_job_outcome(&self, _outcome: &JobOutcome) {}
+
+    /// Called once for every job run with `RayDashboardClient::run_job_with_retry`, after its
+    /// last attempt. Runs through other implementations are not recorded, as for outcomes.
+    fn record_job_retries(&self, _report: &RetryReport) {}
+}
+
+/// Metrics about a single request to the dashboard.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestMetrics {
+    /// Path template of the endpoint with IDs replaced, e.g. `/api/jobs/{submission_id}`
+    pub endpoint: String,
+    pub method: reqwest::Method,
+    /// Response status code, or `None` if no response was received
+    pub status: Option<u16>,
+    pub latency: Duration,
+    /// Size of the request body, e.g. of an uploaded package, or `None` if it had no body
+    pub upload_bytes: Option<u64>,
+}
+
+/// Result of submitting a job.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SubmissionOutcome {
+    /// The dashboard accepted the job
+    Accepted,
+    /// The dashboard responded with an error status
+    Rejected,
+    /// The request failed before the dashboard responded, or the job could not be prepared
+    Failed,
+}
+
+impl SubmissionOutcome {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SubmissionOutcome::Accepted => "accepted",
+            SubmissionOutcome::Rejected => "rejected",
+            SubmissionOutcome::Failed => "failed",
+        }
+    }
+
+    pub(crate) fn from_result<T>(result: &crate::Result<T>) -> Self {
+        match result {
+            Ok(_) => SubmissionOutcome::Accepted,
+            Err(crate::Error::Request(e)) if e.status().is_some() => SubmissionOutcome::Rejected,
+            Err(_) => SubmissionOutcome::Failed,
+        }
+    }
+}
+
+/// Replace the submission IDs and package names in a request path, so endpoints can be used
+/// as metric labels without unbounded cardinality.
+pub(crate) fn endpoint_template(path: &str) -> String {
+    let segments: Vec<&str> = path.trim_start_matches('/').split('/').collect();
+    match segments.as_slice() {
+        ["api", "jobs", id, rest @ ..] if !id.is_empty() => {
+            let mut template = "/api/jobs/{submission_id}".to_string();
+            for segment in rest {
+                template.push('/');
+                template.push_str(segment);
+            }
+            template
+        }
+        ["api", "packages", protocol, _] => format!("/api/packages/{}/{{package}}", protocol),
+        _ => path.to_string(),
+    }
+}
+
+/// Records metrics with the `metrics` crate, using the globally installed recorder.
+///
+/// Emits `ray_dashboard_requests_total`, `ray_dashboard_request_duration_seconds` and
+/// `ray_dashboard_upload_bytes_total` labelled by endpoint, method and status, the latter only for
+/// requests with a body, `ray_dashboard_job_submissions_total` labelled by outcome,
+/// `ray_dashboard_job_outcomes_total` labelled by final status and `ray_dashboard_job_retries_total`.
+#[cfg(feature = "metrics")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MetricsCrateRecorder;
+
+#[cfg(feature = "metrics")]
+impl MetricsRecorder for MetricsCrateRecorder {
+    fn record_request(&self, metrics: &RequestMetrics) {
+        let status = match metrics.status {
+            Some(status) => status.to_string(),
+            None => "error".to_string(),
+        };
+        let labels = [
+            ("endpoint", metrics.endpoint.clone()),
+            ("method", metrics.method.to_string()),
+            ("status", status),
+        ];
+
+        metrics::counter!("ray_dashboard_requests_total", &labels).increment(1);
+        metrics::histogram!("ray_dashboard_request_duration_seconds", &labels)
+            .record(metrics.latency.as_secs_f64());
+        if let Some(upload_bytes) = metrics.upload_bytes {
+            metrics::counter!("ray_dashboard_upload_bytes_total", &labels).increment(upload_bytes);
+        }
+    }
+
+    fn record_job_submission(&self, outcome: SubmissionOutcome) {
+        metrics::counter!("ray_dashboard_job_submissions_total", "outcome" => outcome.as_str()).increment(1);
+    }
+
+    fn record_job_outcome(&self, outcome: &JobOutcome) {
+        let status = match outcome.timed_out {
+            true => "TIMED_OUT".to_string(),
+            false => format!("{:?}", outcome.status()),
+        };
+        metrics::counter!("ray_dashboard_job_outcomes_total", "status" => status).increment(1);
+    }
+
+    fn record_job_retries(&self, report: &RetryReport) {
+        metrics::counter!("ray_dashboard_job_retries_total").increment(report.retries() as u64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_endpoint_template() {
+        assert_eq!(endpoint_template("/api/jobs/"), "/api/jobs/");
+        assert_eq!(
+            endpoint_template("/api/jobs/raysubmit_123"),
+            "/api/jobs/{submission_id}"
+        );
+        assert_eq!(
+            endpoint_template("/api/jobs/raysubmit_123/logs"),
+            "/api/jobs/{submission_id}/logs"
+        );
+        assert_eq!(
+            endpoint_template("/api/packages/gcs/_ray_pkg_abc.zip"),
+            "/api/packages/gcs/{package}"
+        );
+        assert_eq!(endpoint_template("/api/v0/logs/file"), "/api/v0/logs/file");
+    }
+}
@@ -1,14 +1,69 @@
-use tracing::info;
+use std::{
+    pin::pin,
+    sync::Arc,
+    time::{Duration, Instant},
+};
 
-use crate::schemas::common::RayVersionResponse;
+use futures::future::{Either, select};
+use futures_timer::Delay;
+use tokio::sync::OnceCell;
+use tracing::{Instrument, debug, field, info_span, instrument};
 
+use crate::{
+    constants::HEALTH_POLL_INTERVAL_MS,
+    schemas::{
+        capabilities::{RayVersion, ServerCapabilities, ServerFeature},
+        common::{ClusterHealth, ComponentHealth, RayVersionResponse},
+    },
+};
+
+pub mod bulk;
+pub mod cluster;
+mod guard;
+pub mod handle;
 pub mod jobs;
+pub mod logs;
+pub mod metrics;
 pub mod packages;
+pub mod retry;
+pub mod watch;
+
+use cluster::ClusterAPI;
+use jobs::JobSubmissionAPI;
+use logs::LogsAPI;
+use metrics::{MetricsRecorder, RequestMetrics};
+use packages::PackagesAPI;
+
+/// All dashboard APIs combined, usable as `Arc<dyn RayDashboard>` to swap in fakes in tests.
+pub trait RayDashboard: JobSubmissionAPI + PackagesAPI + LogsAPI + ClusterAPI {}
 
-#[derive(Debug, Clone)]
+impl<T> RayDashboard for T where T: JobSubmissionAPI + PackagesAPI + LogsAPI + ClusterAPI + ?Sized {}
+
+/// Returns the W3C `traceparent` header of the current trace, if any.
+pub type TraceparentProvider = Arc<dyn Fn() -> Option<String> + Send + Sync>;
+
+#[derive(Clone)]
 pub struct RayDashboardClient {
     base_url: url::Url,
     client: reqwest::Client,
+    /// Server version fetched on first use, shared between clones
+    server_version: Arc<OnceCell<RayVersionResponse>>,
+    /// Records or replays requests instead of only sending them
+    #[cfg(feature = "testing")]
+    fixture: Option<Arc<crate::testing::HttpFixture>>,
+    traceparent: Option<TraceparentProvider>,
+    metrics: Option<Arc<dyn MetricsRecorder>>,
+    #[cfg(feature = "registry")]
+    registry: Option<Arc<crate::registry::JobRegistry>>,
+}
+
+impl std::fmt::Debug for RayDashboardClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RayDashboardClient")
+            .field("base_url", &self.base_url)
+            .field("client", &self.client)
+            .finish_non_exhaustive()
+    }
 }
 
 impl RayDashboardClient {
@@ -19,7 +74,51 @@ impl RayDashboardClient {
 
     pub fn new_with_client(base_url: &str, client: reqwest::Client) -> crate::Result<Self> {
         let base_url = url::Url::parse(base_url)?;
-        Ok(Self { base_url, client })
+        Ok(Self {
+            base_url,
+            client,
+            server_version: Arc::new(OnceCell::new()),
+            #[cfg(feature = "testing")]
+            fixture: None,
+            traceparent: None,
+            metrics: None,
+            #[cfg(feature = "registry")]
+            registry: None,
+        })
+    }
+
+    /// Send a `traceparent` header from the provider with every request, so traces continue
+    /// through the dashboard. The provider typically reads the current OpenTelemetry context.
+    pub fn with_traceparent_provider<F>(mut self, provider: F) -> Self
+    where
+        F: Fn() -> Option<String> + Send + Sync + 'static,
+    {
+        self.traceparent = Some(Arc::new(provider));
+        self
+    }
+
+    /// Report request latency, status codes and job submissions to a metrics recorder.
+    pub fn with_metrics_recorder(mut self, recorder: Arc<dyn MetricsRecorder>) -> Self {
+        self.metrics = Some(recorder);
+        self
+    }
+
+    /// Record every submitted job in a local registry.
+    #[cfg(feature = "registry")]
+    pub fn with_registry(mut self, registry: Arc<crate::registry::JobRegistry>) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Record requests to, or replay responses from, an HTTP fixture.
+    #[cfg(feature = "testing")]
+    pub fn with_fixture(mut self, fixture: Arc<crate::testing::HttpFixture>) -> Self {
+        self.fixture = Some(fixture);
+        self
+    }
+
+    pub fn base_url(&self) -> &url::Url {
+        &self.base_url
     }
 
     pub async fn ping(&self) -> crate::Result<()> {
@@ -27,22 +126,152 @@ impl RayDashboardClient {
         Ok(())
     }
 
+    #[instrument(skip_all)]
     pub async fn get_version(&self) -> crate::Result<RayVersionResponse> {
         let path = "/api/version";
         let request = self.base_request(reqwest::Method::GET, path)?;
-        let response = request.send().await?.error_for_status()?;
+        let response = self.send(request).await?.error_for_status()?;
         Ok(response.json::<RayVersionResponse>().await?)
     }
 
+    /// Get the server version, fetching it on first use and caching it afterwards.
+    pub async fn server_version(&self) -> crate::Result<&RayVersionResponse> {
+        self.server_version.get_or_try_init(|| self.get_version()).await
+    }
+
+    /// Report which optional APIs the connected server supports, based on its version.
+    pub async fn server_capabilities(&self) -> crate::Result<ServerCapabilities> {
+        let version = RayVersion::parse(&self.server_version().await?.ray_version)?;
+        Ok(ServerCapabilities::from_version(version))
+    }
+
+    /// Return an error if the connected server does not support a feature.
+    pub(crate) async fn require_feature(&self, feature: ServerFeature) -> crate::Result<()> {
+        self.server_capabilities().await?.require(feature)
+    }
+
+    /// Check the health of the dashboard, GCS and the raylet on the dashboard's node.
+    /// Unreachable or failing components are reported as unhealthy rather than as errors.
+    #[instrument(skip_all)]
+    pub async fn health(&self) -> crate::Result<ClusterHealth> {
+        let gcs = self.check_component("/api/gcs_healthz").await?;
+        let raylet = self.check_component("/api/local_raylet_healthz").await?;
+        let version = self.get_version().await.ok();
+        Ok(ClusterHealth { gcs, raylet, version })
+    }
+
+    /// Wait until the cluster reports healthy, e.g. after starting it.
+    /// Return an error if the cluster is not healthy within the timeout.
+    #[instrument(skip(self))]
+    pub async fn wait_until_ready(&self, timeout: Duration) -> crate::Result<ClusterHealth> {
+        let start = Instant::now();
+
+        loop {
+            // Bound each check by the remaining time, so a hung connection cannot outlast the timeout
+            let remaining = timeout.saturating_sub(start.elapsed());
+            let health = match select(pin!(self.health()), Delay::new(remaining)).await {
+                Either::Left((health, _)) => health?,
+                Either::Right(_) => {
+                    return Err(crate::Error::ClusterNotReady {
+                        timeout,
+                        reason: "Health check did not complete in time".to_string(),
+                    });
+                }
+            };
+            let Some(reason) = health.unhealthy_reason() else {
+                return Ok(health);
+            };
+
+            let elapsed = start.elapsed();
+            if elapsed >= timeout {
+                return Err(crate::Error::ClusterNotReady { timeout, reason });
+            }
+
+            debug!("Cluster not ready yet: {}", reason);
+            let delay = Duration::from_millis(HEALTH_POLL_INTERVAL_MS).min(timeout - elapsed);
+            Delay::new(delay).await;
+        }
+    }
+
+    async fn check_component(&self, path: &str) -> crate::Result<ComponentHealth> {
+        let request = self.base_request(reqwest::Method::GET, path)?;
+        let health = match self.send(request).await {
+            Ok(response) => {
+                let healthy = response.status().is_success();
+                let message = response.text().await.ok().filter(|text| !text.is_empty());
+                ComponentHealth { healthy, message }
+            }
+            Err(e) => ComponentHealth {
+                healthy: false,
+                message: Some(e.to_string()),
+            },
+        };
+        Ok(health)
+    }
+
     /// Build base request with common headers.
     /// Ray dashboard server requires User-Agent header to be set or else 500s.
     fn base_request(&self, method: reqwest::Method, path: &str) -> crate::Result<reqwest::RequestBuilder> {
         let url = self.base_url.join(path)?;
-        info!("Building request: {} {}", method, url);
         let request = self
             .client
             .request(method, url)
             .header("User-Agent", crate::constants::SDK_USER_AGENT);
         Ok(request)
     }
+
+    /// Send a request built with `base_request` inside a span recording its method, path,
+    /// status and latency.
+    async fn send(&self, mut request: reqwest::RequestBuilder) -> crate::Result<reqwest::Response> {
+        if let Some(traceparent) = self.traceparent.as_ref().and_then(|provider| provider()) {
+            request = request.header("traceparent", traceparent);
+        }
+        let request = request.build()?;
+
+        let span = info_span!(
+            "ray_dashboard.request",
+            http.method = %request.method(),
+            url.path = request.url().path(),
+            http.status_code = field::Empty,
+            latency_ms = field::Empty,
+        );
+        debug!(parent: &span, "Sending request: {} {}", request.method(), request.url());
+
+        let method = request.method().clone();
+        let endpoint = metrics::endpoint_template(request.url().path());
+        let upload_bytes = request
+            .body()
+            .map(|body| body.as_bytes().map_or(0, |bytes| bytes.len() as u64));
+
+        let start = Instant::now();
+        let result = self.execute(request).instrument(span.clone()).await;
+        let latency = start.elapsed();
+        span.record("latency_ms", latency.as_millis() as u64);
+        match result {
+            Ok(ref response) => {
+                span.record("http.status_code", response.status().as_u16());
+                debug!(parent: &span, "Received response: {}", response.status());
+            }
+            Err(ref e) => debug!(parent: &span, "Request failed: {}", e),
+        }
+
+        if let Some(ref recorder) = self.metrics {
+            recorder.record_request(&RequestMetrics {
+                endpoint,
+                method,
+                status: result.as_ref().ok().map(|response| response.status().as_u16()),
+                latency,
+                upload_bytes,
+            });
+        }
+        result
+    }
+
+    async fn execute(&self, request: reqwest::Request) -> crate::Result<reqwest::Response> {
+        #[cfg(feature = "testing")]
+        if let Some(ref fixture) = self.fixture {
+            return fixture.execute(&self.client, request).await;
+        }
+        Ok(self.client.execute(request).await?)
+    }
 }
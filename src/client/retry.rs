@@ -0,0 +1,338 @@
+use std::{fmt, sync::Arc, time::Duration};
+
+use futures_timer::Delay;
+use tracing::debug;
+
+use crate::{
+    client::jobs::{JobOutcome, JobSubmissionAPI, RunOptions},
+    schemas::jobs::{JobStatus, JobSubmitRequest},
+};
+
+/// Default delay before the first retry.
+const DEFAULT_INITIAL_BACKOFF_MS: u64 = 5_000;
+/// Default upper bound for the delay between retries.
+const DEFAULT_MAX_BACKOFF_MS: u64 = 300_000;
+
+/// Predicate matched against the message of an unsuccessful job.
+pub type MessagePredicate = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// Condition under which an unsuccessful job is retried.
+#[derive(Clone)]
+pub enum RetryCondition {
+    /// The job failed with this `error_type`, e.g. `JOB_SUPERVISOR_ACTOR_DIED`
+    ErrorType(String),
+    /// The job driver exited with this code
+    ExitCode(i32),
+    /// The job message matches this predicate, e.g. a regular expression
+    Message(MessagePredicate),
+    /// The job did not finish within the timeout of the run options
+    TimedOut,
+}
+
+impl fmt::Debug for RetryCondition {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetryCondition::ErrorType(error_type) => f.debug_tuple("ErrorType").field(error_type).finish(),
+            RetryCondition::ExitCode(code) => f.debug_tuple("ExitCode").field(code).finish(),
+            RetryCondition::Message(_) => f.debug_tuple("Message").finish_non_exhaustive(),
+            RetryCondition::TimedOut => f.write_str("TimedOut"),
+        }
+    }
+}
+
+impl RetryCondition {
+    fn matches(&self, outcome: &JobOutcome) -> bool {
+        let details = &outcome.details;
+        match self {
+            RetryCondition::ErrorType(error_type) => details.error_type.as_ref() == Some(error_type),
+            RetryCondition::ExitCode(code) => details.driver_exit_code == Some(*code),
+            RetryCondition::Message(predicate) => details
+                .message
+                .as_deref()
+                .is_some_and(|message| predicate(message)),
+            RetryCondition::TimedOut => outcome.timed_out,
+        }
+    }
+}
+
+/// When and how often [`JobSubmissionAPI::run_job_with_retry`] resubmits unsuccessful jobs.
+///
+/// Without conditions, every `FAILED` job is retried. With conditions, only jobs matching
+/// at least one of them are. Jobs that were stopped are never retried unless they timed out
+/// and [`RetryCondition::TimedOut`] is set.
+///
+/// [`JobSubmissionAPI::run_job_with_retry`]: crate::JobSubmissionAPI::run_job_with_retry
+#[derive(Debug, Clone)]
+pub struct JobRetryPolicy {
+    /// Maximum number of resubmissions after the first attempt
+    pub max_retries: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Upper bound for the delay between retries
+    pub max_backoff: Duration,
+    /// Factor the delay is multiplied by after every retry
+    pub backoff_factor: f64,
+    pub conditions: Vec<RetryCondition>,
+}
+
+impl Default for JobRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(DEFAULT_INITIAL_BACKOFF_MS),
+            max_backoff: Duration::from_millis(DEFAULT_MAX_BACKOFF_MS),
+            backoff_factor: 2.0,
+            conditions: Vec::new(),
+        }
+    }
+}
+
+impl JobRetryPolicy {
+    /// Create a policy retrying every failed job up to `max_retries` times.
+    pub fn new(max_retries: u32) -> Self {
+        Self {
+            max_retries,
+            ..Default::default()
+        }
+    }
+
+    /// Set the delay before the first retry and the upper bound for later delays.
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.initial_backoff = initial;
+        self.max_backoff = max;
+        self
+    }
+
+    /// Set the factor the delay is multiplied by after every retry.
+    /// A factor of `1.0` retries at a fixed delay.
+    pub fn with_backoff_factor(mut self, factor: f64) -> Self {
+        self.backoff_factor = factor;
+        self
+    }
+
+    /// Only retry jobs matching this condition or another one added before.
+    pub fn with_condition(mut self, condition: RetryCondition) -> Self {
+        self.conditions.push(condition);
+        self
+    }
+
+    /// Retry jobs that failed with this `error_type`.
+    pub fn retry_on_error_type(self, error_type: impl Into<String>) -> Self {
+        self.with_condition(RetryCondition::ErrorType(error_type.into()))
+    }
+
+    /// Retry jobs whose driver exited with this code.
+    pub fn retry_on_exit_code(self, code: i32) -> Self {
+        self.with_condition(RetryCondition::ExitCode(code))
+    }
+
+    /// Retry jobs whose message matches a predicate, e.g. `move |message| pattern.is_match(message)`
+    /// with a regular expression.
+    pub fn retry_on_message<F>(self, predicate: F) -> Self
+    where
+        F: Fn(&str) -> bool + Send + Sync + 'static,
+    {
+        self.with_condition(RetryCondition::Message(Arc::new(predicate)))
+    }
+
+    /// Whether an unsuccessful outcome should be retried, ignoring the number of attempts.
+    pub fn should_retry(&self, outcome: &JobOutcome) -> bool {
+        if outcome.is_success() {
+            return false;
+        }
+        if outcome.status() == JobStatus::STOPPED {
+            let retry_timeouts = self
+                .conditions
+                .iter()
+                .any(|condition| matches!(condition, RetryCondition::TimedOut));
+            return outcome.timed_out && retry_timeouts;
+        }
+        if self.conditions.is_empty() {
+            return outcome.status() == JobStatus::FAILED;
+        }
+        self.conditions.iter().any(|condition| condition.matches(outcome))
+    }
+
+    /// Delay before the given retry, starting at 1.
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = retry.saturating_sub(1).min(i32::MAX as u32) as i32;
+        let factor = self.backoff_factor.max(1.0).powi(exponent);
+        Duration::try_from_secs_f64(self.initial_backoff.as_secs_f64() * factor)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+
+    /// Submission ID of the given retry, derived from the submission ID requested for the job.
+    pub fn retry_submission_id(submission_id: &str, retry: u32) -> String {
+        format!("{}-retry-{}", submission_id, retry)
+    }
+}
+
+/// A single submission made by [`JobSubmissionAPI::run_job_with_retry`].
+///
+/// [`JobSubmissionAPI::run_job_with_retry`]: crate::JobSubmissionAPI::run_job_with_retry
+#[derive(Debug)]
+pub struct JobAttempt {
+    /// 0 for the first attempt, then the number of the retry
+    pub retry: u32,
+    /// Submission ID of the attempt as returned by the API it ran through, or `None` if Ray was left
+    /// to generate it and the submission failed
+    pub submission_id: Option<String>,
+    /// Outcome of the job, or the error that prevented it from being submitted or waited for
+    pub outcome: crate::Result<JobOutcome>,
+}
+
+/// Every attempt made to run a job, in order. The last attempt is the final one.
+#[derive(Debug, Default)]
+pub struct RetryReport {
+    pub attempts: Vec<JobAttempt>,
+}
+
+impl RetryReport {
+    /// The last attempt.
+    pub fn last(&self) -> Option<&JobAttempt> {
+        self.attempts.last()
+    }
+
+    /// Number of resubmissions after the first attempt.
+    pub fn retries(&self) -> u32 {
+        self.attempts.len().saturating_sub(1) as u32
+    }
+
+    /// Whether the last attempt succeeded.
+    pub fn is_success(&self) -> bool {
+        self.last()
+            .is_some_and(|attempt| matches!(attempt.outcome, Ok(ref outcome) if outcome.is_success()))
+    }
+
+    /// Outcome of the last attempt, as an error if it did not succeed.
+    pub fn into_result(mut self) -> crate::Result<JobOutcome> {
+        match self.attempts.pop() {
+            Some(attempt) => attempt.outcome?.into_result(),
+            None => Err(crate::Error::Generic("No job was submitted".to_string())),
+        }
+    }
+}
+
+/// Run a job with `run_job`, resubmitting it while the policy allows,
+/// see [`JobSubmissionAPI::run_job_with_retry`].
+pub(crate) async fn run_with_retry<C>(
+    client: &C,
+    payload: &JobSubmitRequest,
+    options: RunOptions,
+    policy: &JobRetryPolicy,
+) -> RetryReport
+where
+    C: JobSubmissionAPI + ?Sized,
+{
+    let mut report = RetryReport::default();
+    let requested_submission_id = payload.submission_id.clone();
+    let mut payload = payload.clone();
+
+    for retry in 0..=policy.max_retries {
+        if retry > 0 {
+            let delay = policy.backoff(retry);
+            debug!(
+                "Retrying job in {:?} (retry {} of {})",
+                delay, retry, policy.max_retries
+            );
+            Delay::new(delay).await;
+            payload.submission_id = requested_submission_id
+                .as_deref()
+                .map(|submission_id| JobRetryPolicy::retry_submission_id(submission_id, retry));
+        }
+
+        let outcome = client.run_job(&payload, options.clone()).await;
+        let submission_id = match outcome {
+            Ok(ref outcome) => Some(outcome.submission_id.clone()),
+            Err(_) => payload.submission_id.clone(),
+        };
+
+        let should_retry = matches!(outcome, Ok(ref outcome) if policy.should_retry(outcome));
+        report.attempts.push(JobAttempt {
+            retry,
+            submission_id,
+            outcome,
+        });
+        if !should_retry {
+            break;
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::jobs::JobDetails;
+
+    fn outcome(status: JobStatus, error_type: Option<&str>, message: Option<&str>) -> JobOutcome {
+        JobOutcome {
+            submission_id: "job".to_string(),
+            details: JobDetails {
+                message: message.map(String::from),
+                error_type: error_type.map(String::from),
+                driver_exit_code: Some(1),
+                ..JobDetails::test_job("job", status)
+            },
+            logs: None,
+            timed_out: false,
+        }
+    }
+
+    #[test]
+    fn test_should_retry() {
+        let failed = outcome(
+            JobStatus::FAILED,
+            Some("JOB_SUPERVISOR_ACTOR_DIED"),
+            Some("Node died"),
+        );
+        let stopped = outcome(JobStatus::STOPPED, None, None);
+        let succeeded = outcome(JobStatus::SUCCEEDED, None, None);
+
+        let any_failure = JobRetryPolicy::new(2);
+        assert!(any_failure.should_retry(&failed));
+        assert!(!any_failure.should_retry(&stopped));
+        assert!(!any_failure.should_retry(&succeeded));
+
+        let preemption = JobRetryPolicy::new(2).retry_on_message(|message| message.contains("died"));
+        assert!(preemption.should_retry(&failed));
+        assert!(!preemption.should_retry(&outcome(JobStatus::FAILED, None, Some("Out of memory"))));
+
+        let exit_code = JobRetryPolicy::new(2).retry_on_exit_code(1);
+        assert!(exit_code.should_retry(&failed));
+        assert!(!exit_code.should_retry(&stopped));
+        let mut timed_out = stopped;
+        timed_out.timed_out = true;
+        assert!(!exit_code.should_retry(&timed_out));
+        assert!(
+            exit_code
+                .with_condition(RetryCondition::TimedOut)
+                .should_retry(&timed_out)
+        );
+
+        assert!(
+            JobRetryPolicy::new(2)
+                .retry_on_error_type("JOB_SUPERVISOR_ACTOR_DIED")
+                .should_retry(&failed)
+        );
+        assert!(
+            !JobRetryPolicy::new(2)
+                .retry_on_exit_code(137)
+                .should_retry(&failed)
+        );
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = JobRetryPolicy::new(5).with_backoff(Duration::from_secs(1), Duration::from_secs(5));
+        let delays: Vec<_> = (1..=4).map(|retry| policy.backoff(retry).as_secs()).collect();
+        assert_eq!(delays, [1, 2, 4, 5]);
+
+        let policy = JobRetryPolicy::new(u32::MAX).with_backoff_factor(1e300);
+        assert_eq!(policy.backoff(u32::MAX), policy.max_backoff);
+        let policy = policy.with_backoff_factor(f64::NAN);
+        assert_eq!(policy.backoff(u32::MAX), policy.initial_backoff);
+        assert_eq!(JobRetryPolicy::retry_submission_id("train", 2), "train-retry-2");
+    }
+}
@@ -0,0 +1,274 @@
+use std::{
+    collections::{BTreeMap, HashSet},
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
+
+use futures::{
+    StreamExt,
+    stream::{self, BoxStream},
+};
+use futures_timer::Delay;
+use reqwest::StatusCode;
+use tracing::warn;
+
+use crate::{
+    client::jobs::JobSubmissionAPI,
+    schemas::jobs::{JobDetails, JobStatus},
+};
+
+/// Predicate selecting the jobs watched by [`JobSubmissionAPI::watch_jobs`].
+pub type JobFilter = Arc<dyn Fn(&JobDetails) -> bool + Send + Sync>;
+
+/// A change in the status or message of a single job.
+#[derive(Debug, Clone)]
+pub struct JobStatusEvent {
+    pub submission_id: String,
+    /// Status before this change, or `None` for the first observation
+    pub previous_status: Option<JobStatus>,
+    pub status: JobStatus,
+    pub message: Option<String>,
+    /// When the change was observed by the client
+    pub observed_at: SystemTime,
+    pub details: JobDetails,
+}
+
+/// A change in the set of jobs known to the cluster.
+#[derive(Debug, Clone)]
+pub enum JobsEvent {
+    /// A job was seen for the first time
+    Created(JobDetails),
+    /// The status or message of a known job changed
+    Updated {
+        previous_status: JobStatus,
+        previous_message: Option<String>,
+        current: JobDetails,
+    },
+    /// A known job is no longer listed, e.g. because it was deleted
+    Removed(JobDetails),
+}
+
+/// Watch a single job, see [`JobSubmissionAPI::watch_job`].
+pub(crate) fn watch_job<'a, C>(
+    client: &'a C,
+    submission_id: &str,
+    poll_interval: Duration,
+) -> BoxStream<'a, crate::Result<JobStatusEvent>>
+where
+    C: JobSubmissionAPI + ?Sized,
+{
+    struct State<'a, C: ?Sized> {
+        client: &'a C,
+        submission_id: String,
+        poll_interval: Duration,
+        last: Option<(JobStatus, Option<String>)>,
+        polled: bool,
+        done: bool,
+    }
+
+    let state = State {
+        client,
+        submission_id: submission_id.to_string(),
+        poll_interval,
+        last: None,
+        polled: false,
+        done: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        loop {
+            if state.done {
+                return None;
+            }
+            if state.polled {
+                Delay::new(state.poll_interval).await;
+            }
+            state.polled = true;
+
+            let details = match state.client.get_job_details(&state.submission_id).await {
+                Ok(details) => details,
+                Err(e) => {
+                    // The job does not exist or was deleted, so polling again cannot succeed
+                    state.done = matches!(
+                        e,
+                        crate::Error::Request(ref e) if e.status() == Some(StatusCode::NOT_FOUND)
+                    );
+                    return Some((Err(e), state));
+                }
+            };
+
+            let current = (details.status, details.message.clone());
+            if state.last.as_ref() == Some(&current) {
+                continue;
+            }
+
+            state.done = details.status.is_terminal();
+            let event = JobStatusEvent {
+                submission_id: state.submission_id.clone(),
+                previous_status: state.last.as_ref().map(|(status, _)| *status),
+                status: details.status,
+                message: details.message.clone(),
+                observed_at: SystemTime::now(),
+                details,
+            };
+            state.last = Some(current);
+
+            return Some((Ok(event), state));
+        }
+    })
+    .boxed()
+}
+
+/// Watch all jobs matching a filter, see [`JobSubmissionAPI::watch_jobs`].
+pub(crate) fn watch_jobs<C>(
+    client: &C,
+    filter: JobFilter,
+    poll_interval: Duration,
+) -> BoxStream<'_, crate::Result<JobsEvent>>
+where
+    C: JobSubmissionAPI + ?Sized,
+{
+    let state = (Listing::default(), false);
+
+    stream::unfold(state, move |(mut known, polled)| {
+        let filter = filter.clone();
+
+        async move {
+            if polled {
+                Delay::new(poll_interval).await;
+            }
+
+            let events = match client.list_jobs().await {
+                Ok(jobs) => {
+                    let current: Vec<JobDetails> = jobs.into_iter().filter(|job| filter(job)).collect();
+                    known.update(current).into_iter().map(Ok).collect()
+                }
+                Err(e) => vec![Err(e)],
+            };
+            Some((stream::iter(events), (known, true)))
+        }
+    })
+    .flatten()
+    .boxed()
+}
+
+/// Jobs seen in the last listing of a watch.
+#[derive(Debug, Default)]
+struct Listing {
+    jobs: BTreeMap<String, JobDetails>,
+    /// Jobs without an ID that a warning was logged for, by entrypoint and start time
+    unkeyed: HashSet<(String, Option<u64>)>,
+}
+
+impl Listing {
+    /// Replace the known jobs with a new listing and return the changes, in listing order,
+    /// followed by the removed jobs sorted by key.
+    fn update(&mut self, current: Vec<JobDetails>) -> Vec<JobsEvent> {
+        let mut keyed = Vec::with_capacity(current.len());
+        let mut unkeyed = HashSet::new();
+        for job in current {
+            match job_key(&job) {
+                Some(key) => keyed.push((key, job)),
+                None => {
+                    let id = (job.entrypoint.clone(), job.start_time);
+                    if !self.unkeyed.contains(&id) {
+                        warn!(
+                            "Not watching job with entrypoint {:?}, which has no submission or driver ID",
+                            job.entrypoint
+                        );
+                    }
+                    unkeyed.insert(id);
+                }
+            }
+        }
+        self.unkeyed = unkeyed;
+
+        let mut events = Vec::new();
+        let mut previous = std::mem::take(&mut self.jobs);
+        for (key, job) in keyed {
+            match previous.remove(&key) {
+                None => events.push(JobsEvent::Created(job.clone())),
+                Some(previous) if previous.status != job.status || previous.message != job.message => {
+                    events.push(JobsEvent::Updated {
+                        previous_status: previous.status,
+                        previous_message: previous.message,
+                        current: job.clone(),
+                    });
+                }
+                Some(_) => {}
+            }
+            self.jobs.insert(key, job);
+        }
+        events.extend(previous.into_values().map(JobsEvent::Removed));
+        events
+    }
+}
+
+/// Key identifying a job across listings.
+/// Driver jobs have no submission ID, so fall back to the driver's job ID.
+/// Jobs with neither cannot be told apart between listings and are skipped.
+fn job_key(job: &JobDetails) -> Option<String> {
+    job.submission_id
+        .clone()
+        .or_else(|| job.driver_info.as_ref().map(|info| info.id.clone()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::jobs::JobType;
+
+    fn job(submission_id: &str, status: JobStatus) -> JobDetails {
+        JobDetails::test_job(submission_id, status)
+    }
+
+    fn keys(events: &[JobsEvent]) -> Vec<String> {
+        events
+            .iter()
+            .map(|event| match event {
+                JobsEvent::Created(job) => format!("created:{}", job.submission_id.as_deref().unwrap()),
+                JobsEvent::Updated { current, .. } => {
+                    format!("updated:{}", current.submission_id.as_deref().unwrap())
+                }
+                JobsEvent::Removed(job) => format!("removed:{}", job.submission_id.as_deref().unwrap()),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_diff_jobs() {
+        let mut known = Listing::default();
+
+        let events = known.update(vec![job("a", JobStatus::PENDING)]);
+        assert!(
+            matches!(events.as_slice(), [JobsEvent::Created(job)] if job.job_type == JobType::SUBMISSION)
+        );
+
+        let events = known.update(vec![job("a", JobStatus::PENDING)]);
+        assert!(events.is_empty());
+
+        let events = known.update(vec![job("c", JobStatus::PENDING), job("a", JobStatus::RUNNING)]);
+        assert_eq!(keys(&events), ["created:c", "updated:a"]);
+        assert!(events.iter().any(|event| matches!(
+            event,
+            JobsEvent::Updated { previous_status, current, .. }
+                if *previous_status == JobStatus::PENDING && current.status == JobStatus::RUNNING
+        )));
+
+        let events = known.update(vec![job("b", JobStatus::PENDING)]);
+        assert_eq!(keys(&events), ["created:b", "removed:a", "removed:c"]);
+    }
+
+    #[test]
+    fn test_jobs_without_id_are_skipped() {
+        let mut driver = job("a", JobStatus::RUNNING);
+        driver.submission_id = None;
+        assert_eq!(job_key(&driver), None);
+
+        let mut known = Listing::default();
+        assert!(known.update(vec![driver.clone(), driver.clone()]).is_empty());
+        assert_eq!(known.unkeyed.len(), 1);
+        assert!(known.update(vec![]).is_empty());
+        assert!(known.unkeyed.is_empty());
+    }
+}
@@ -6,3 +6,18 @@ pub const RAY_PKG_PREFIX: &str = "_ray_pkg_";
 
 /// Protocol for Ray package URIs
 pub const RAY_PKG_PROTOCOL: &str = "gcs";
+
+/// Initial interval between job status polls
+pub const JOB_POLL_INTERVAL_MS: u64 = 500;
+
+/// Upper bound for the interval between job status polls when backing off
+pub const JOB_POLL_MAX_INTERVAL_MS: u64 = 5000;
+
+/// How long to wait for a job to reach a terminal state after requesting it to stop
+pub const JOB_STOP_GRACE_PERIOD_SECS: u64 = 10;
+
+/// Interval between health checks while waiting for a cluster to become ready
+pub const HEALTH_POLL_INTERVAL_MS: u64 = 1000;
+
+/// Largest number of results the state API returns for a single list request
+pub const STATE_API_MAX_LIMIT: usize = 10_000;
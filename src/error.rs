@@ -1,5 +1,9 @@
+use std::time::Duration;
+
 use thiserror::Error;
 
+use crate::schemas::jobs::JobStatus;
+
 pub type Result<T> = std::result::Result<T, Error>;
 
 #[derive(Debug, Error)]
@@ -12,6 +16,35 @@ pub enum Error {
     UrlParse(#[from] url::ParseError),
     #[error("Zip Error: {0}")]
     Zip(#[from] zip::result::ZipError),
+    #[error("Job {submission_id} did not succeed (status: {status:?}): {}", message.as_deref().unwrap_or("no message"))]
+    JobFailed {
+        submission_id: String,
+        status: JobStatus,
+        message: Option<String>,
+    },
+    #[error("Job {submission_id} did not reach a terminal state in time (last status: {last_status:?})")]
+    Timeout {
+        submission_id: String,
+        last_status: JobStatus,
+    },
+    #[error("Waiting for job {submission_id} was cancelled")]
+    Cancelled { submission_id: String },
+    #[error("Cluster did not become ready within {timeout:?}: {reason}")]
+    ClusterNotReady { timeout: Duration, reason: String },
+    #[error("{feature} is not supported by Ray {server_version}")]
+    Unsupported { feature: String, server_version: String },
+    #[error("No cluster named {0} in the pool")]
+    UnknownCluster(String),
+    #[error("No cluster available for the job: {0}")]
+    NoClusterAvailable(String),
+    #[error("Invalid cluster name {0:?}: names must not be empty or contain ':'")]
+    InvalidClusterName(String),
+    #[error("Invalid environment variable name: {0:?}")]
+    InvalidEnvVar(String),
+    #[error("Invalid job graph: {0}")]
+    InvalidGraph(String),
+    #[error("Job exceeds the queue quota: {0}")]
+    ExceedsQuota(String),
     #[error("Generic Error: {0}")]
     Generic(String),
 }
@@ -1,10 +1,28 @@
+#[cfg(feature = "blocking")]
+pub mod blocking;
 pub mod client;
 mod constants;
 mod error;
+pub mod orchestration;
+pub mod pool;
+pub mod queue;
+#[cfg(feature = "registry")]
+pub mod registry;
+#[cfg(feature = "scheduler")]
+pub mod scheduler;
 pub mod schemas;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod utils;
 
-pub use crate::client::RayDashboardClient;
-pub use crate::client::jobs::JobSubmissionAPI;
+pub use crate::client::cluster::ClusterAPI;
+pub use crate::client::handle::JobHandle;
+pub use crate::client::jobs::{JobOutcome, JobSubmissionAPI, RunOptions, WaitOptions};
+pub use crate::client::logs::LogsAPI;
+pub use crate::client::metrics::{MetricsRecorder, RequestMetrics, SubmissionOutcome};
 pub use crate::client::packages::PackagesAPI;
+pub use crate::client::retry::{JobRetryPolicy, RetryCondition, RetryReport};
+pub use crate::client::{RayDashboard, RayDashboardClient};
 pub use crate::error::{Error, Result};
+pub use crate::pool::RayClusterPool;
+pub use crate::queue::SubmissionQueue;
@@ -0,0 +1,334 @@
+//! Running jobs that depend on each other.
+//!
+//! A [`JobGraph`] is a directed acyclic graph of named jobs. Running it submits each job once all
+//! of its dependencies succeeded, with bounded parallelism. When a job fails, the jobs depending
+//! on it are skipped while independent branches keep running.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use futures::{StreamExt, stream::FuturesUnordered};
+use tracing::{debug, instrument};
+
+use crate::{
+    client::jobs::{JobOutcome, JobSubmissionAPI, RunOptions},
+    schemas::jobs::JobSubmitRequest,
+};
+
+#[derive(Debug, Clone)]
+struct JobNode {
+    name: String,
+    payload: JobSubmitRequest,
+}
+
+/// Jobs connected by dependencies, run with [`JobGraph::run`].
+#[derive(Debug, Clone, Default)]
+pub struct JobGraph {
+    nodes: Vec<JobNode>,
+    /// Pairs of job names and the jobs they depend on
+    dependencies: Vec<(String, String)>,
+}
+
+/// Result of running a single job of a graph.
+#[derive(Debug)]
+pub enum NodeOutcome {
+    /// The job finished successfully
+    Succeeded(JobOutcome),
+    /// The job finished without succeeding, or timed out
+    Failed(JobOutcome),
+    /// The job could not be submitted or waited for
+    Error(crate::Error),
+    /// The job was not submitted because a dependency did not succeed
+    Skipped { dependency: String },
+}
+
+impl NodeOutcome {
+    pub fn is_success(&self) -> bool {
+        matches!(self, NodeOutcome::Succeeded(_))
+    }
+
+    /// Outcome of the job if it was run to completion.
+    pub fn job_outcome(&self) -> Option<&JobOutcome> {
+        match self {
+            NodeOutcome::Succeeded(outcome) | NodeOutcome::Failed(outcome) => Some(outcome),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct NodeResult {
+    pub name: String,
+    pub outcome: NodeOutcome,
+}
+
+/// Per-job results of running a graph, in the order the jobs were added.
+#[derive(Debug, Default)]
+pub struct GraphReport {
+    pub results: Vec<NodeResult>,
+}
+
+impl GraphReport {
+    /// Result of the named job.
+    pub fn get(&self, name: &str) -> Option<&NodeOutcome> {
+        self.results
+            .iter()
+            .find(|result| result.name == name)
+            .map(|result| &result.outcome)
+    }
+
+    /// Results for jobs that finished successfully.
+    pub fn succeeded(&self) -> impl Iterator<Item = &NodeResult> {
+        self.results.iter().filter(|result| result.outcome.is_success())
+    }
+
+    /// Results for jobs that failed or could not be run.
+    pub fn failed(&self) -> impl Iterator<Item = &NodeResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, NodeOutcome::Failed(_) | NodeOutcome::Error(_)))
+    }
+
+    /// Results for jobs skipped because a dependency did not succeed.
+    pub fn skipped(&self) -> impl Iterator<Item = &NodeResult> {
+        self.results
+            .iter()
+            .filter(|result| matches!(result.outcome, NodeOutcome::Skipped { .. }))
+    }
+
+    /// Whether every job succeeded.
+    pub fn is_success(&self) -> bool {
+        self.results.iter().all(|result| result.outcome.is_success())
+    }
+}
+
+impl JobGraph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a job. Names identify jobs within the graph and are independent of submission IDs.
+    pub fn with_job(mut self, name: impl Into<String>, payload: JobSubmitRequest) -> Self {
+        self.nodes.push(JobNode {
+            name: name.into(),
+            payload,
+        });
+        self
+    }
+
+    /// Only run the job `name` once the job `dependency` succeeded.
+    pub fn with_dependency(mut self, name: impl Into<String>, dependency: impl Into<String>) -> Self {
+        self.dependencies.push((name.into(), dependency.into()));
+        self
+    }
+
+    /// Names of the jobs the named job depends on.
+    fn dependencies_of<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> {
+        self.dependencies
+            .iter()
+            .filter(move |(job, _)| job == name)
+            .map(|(_, dependency)| dependency.as_str())
+    }
+
+    /// Names of the jobs in the order they were added.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.nodes.iter().map(|node| node.name.as_str())
+    }
+
+    /// Check that job names are unique, dependencies exist and there are no cycles.
+    pub fn validate(&self) -> crate::Result<()> {
+        let mut names = HashSet::new();
+        for node in &self.nodes {
+            if !names.insert(node.name.as_str()) {
+                return Err(crate::Error::InvalidGraph(format!(
+                    "job {} is added more than once",
+                    node.name
+                )));
+            }
+        }
+        for (name, dependency) in &self.dependencies {
+            if let Some(unknown) = [name, dependency]
+                .into_iter()
+                .find(|name| !names.contains(name.as_str()))
+            {
+                return Err(crate::Error::InvalidGraph(format!(
+                    "dependency of {} on {} refers to unknown job {}",
+                    name, dependency, unknown
+                )));
+            }
+        }
+
+        let order = self.topological_order();
+        if order.len() < self.nodes.len() {
+            let cyclic: Vec<&str> = self
+                .nodes
+                .iter()
+                .enumerate()
+                .filter(|(index, _)| !order.contains(index))
+                .map(|(_, node)| node.name.as_str())
+                .collect();
+            return Err(crate::Error::InvalidGraph(format!(
+                "dependency cycle between jobs {}",
+                cyclic.join(", ")
+            )));
+        }
+        Ok(())
+    }
+
+    /// Indices of the nodes ordered so that dependencies come first.
+    /// Nodes in a cycle are left out.
+    fn topological_order(&self) -> Vec<usize> {
+        let index: HashMap<&str, usize> = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(i, node)| (node.name.as_str(), i))
+            .collect();
+        let mut remaining = vec![0; self.nodes.len()];
+        for (name, _) in &self.dependencies {
+            remaining[index[name.as_str()]] += 1;
+        }
+        let mut ready: VecDeque<usize> = (0..self.nodes.len()).filter(|&i| remaining[i] == 0).collect();
+
+        let mut order = Vec::with_capacity(self.nodes.len());
+        while let Some(i) = ready.pop_front() {
+            order.push(i);
+            for (name, _) in self
+                .dependencies
+                .iter()
+                .filter(|(_, dependency)| *dependency == self.nodes[i].name)
+            {
+                let j = index[name.as_str()];
+                remaining[j] -= 1;
+                if remaining[j] == 0 {
+                    ready.push_back(j);
+                }
+            }
+        }
+        order
+    }
+
+    /// Run the graph, with at most `concurrency` jobs running at once.
+    ///
+    /// Every job is run with `run_job` and the given options. Returns an error without
+    /// submitting anything if the graph is invalid, and a per-job report otherwise.
+    #[instrument(skip_all, fields(jobs = self.nodes.len()))]
+    pub async fn run<C>(
+        &self,
+        client: &C,
+        concurrency: usize,
+        options: RunOptions,
+    ) -> crate::Result<GraphReport>
+    where
+        C: JobSubmissionAPI + ?Sized,
+    {
+        self.validate()?;
+
+        let mut outcomes: HashMap<&str, NodeOutcome> = HashMap::new();
+        let mut started: HashSet<&str> = HashSet::new();
+        let mut running = FuturesUnordered::new();
+
+        loop {
+            // Skip jobs with a dependency that did not succeed, until nothing changes
+            let mut changed = true;
+            while changed {
+                changed = false;
+                for node in &self.nodes {
+                    if started.contains(node.name.as_str()) {
+                        continue;
+                    }
+                    let failed = self.dependencies_of(&node.name).find(|dependency| {
+                        outcomes
+                            .get(dependency)
+                            .is_some_and(|outcome| !outcome.is_success())
+                    });
+                    if let Some(dependency) = failed {
+                        debug!(
+                            "Skipping job {} because {} did not succeed",
+                            node.name, dependency
+                        );
+                        started.insert(&node.name);
+                        outcomes.insert(
+                            &node.name,
+                            NodeOutcome::Skipped {
+                                dependency: dependency.to_string(),
+                            },
+                        );
+                        changed = true;
+                    }
+                }
+            }
+
+            for node in &self.nodes {
+                if running.len() >= concurrency.max(1) {
+                    break;
+                }
+                let ready = !started.contains(node.name.as_str())
+                    && self
+                        .dependencies_of(&node.name)
+                        .all(|dependency| outcomes.get(dependency).is_some_and(NodeOutcome::is_success));
+                if ready {
+                    debug!("Starting job {}", node.name);
+                    started.insert(&node.name);
+                    let options = options.clone();
+                    running.push(async move {
+                        let outcome = match client.run_job(&node.payload, options).await {
+                            Ok(outcome) if outcome.is_success() => NodeOutcome::Succeeded(outcome),
+                            Ok(outcome) => NodeOutcome::Failed(outcome),
+                            Err(e) => NodeOutcome::Error(e),
+                        };
+                        (node.name.as_str(), outcome)
+                    });
+                }
+            }
+
+            match running.next().await {
+                Some((name, outcome)) => {
+                    debug!("Job {} finished: success={}", name, outcome.is_success());
+                    outcomes.insert(name, outcome);
+                }
+                None => break,
+            }
+        }
+
+        let results = self
+            .nodes
+            .iter()
+            .map(|node| NodeResult {
+                name: node.name.clone(),
+                outcome: outcomes
+                    .remove(node.name.as_str())
+                    .expect("Every job of a valid graph is run or skipped"),
+            })
+            .collect();
+        Ok(GraphReport { results })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn graph() -> JobGraph {
+        JobGraph::new()
+            .with_job("preprocess", JobSubmitRequest::new("python preprocess.py"))
+            .with_job("train", JobSubmitRequest::new("python train.py"))
+            .with_job("evaluate", JobSubmitRequest::new("python evaluate.py"))
+            .with_dependency("train", "preprocess")
+            .with_dependency("evaluate", "train")
+    }
+
+    #[test]
+    fn test_validate_graph() {
+        assert!(graph().validate().is_ok());
+        assert_eq!(graph().topological_order(), [0, 1, 2]);
+
+        let cyclic = graph().with_dependency("preprocess", "evaluate");
+        assert!(matches!(cyclic.validate(), Err(crate::Error::InvalidGraph(_))));
+
+        let unknown = graph().with_dependency("train", "download");
+        assert!(matches!(unknown.validate(), Err(crate::Error::InvalidGraph(_))));
+
+        let duplicate = graph().with_job("train", JobSubmitRequest::new("python train.py"));
+        assert!(matches!(duplicate.validate(), Err(crate::Error::InvalidGraph(_))));
+    }
+}
@@ -0,0 +1,505 @@
+//! Routing jobs across several Ray clusters.
+//!
+//! A [`RayClusterPool`] holds named clients and picks a cluster for each submitted job with a
+//! [`ClusterSelector`]. It implements [`JobSubmissionAPI`] with submission IDs prefixed by the
+//! cluster name, e.g. `us-east:raysubmit_123`, so later calls are routed to the right cluster.
+
+use std::{
+    collections::HashMap,
+    fmt,
+    str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use async_trait::async_trait;
+use futures::future::{join_all, try_join_all};
+use tracing::{debug, instrument, warn};
+
+use crate::{
+    ClusterAPI, JobOutcome, JobSubmissionAPI, RayDashboardClient, RunOptions,
+    schemas::jobs::{
+        JobDeleteResponse, JobDetails, JobLogsResponse, JobStopResponse, JobSubmitRequest, JobSubmitResponse,
+    },
+};
+
+/// Separator between the cluster name and the submission ID in a [`PoolJobId`].
+pub const POOL_JOB_ID_SEPARATOR: char = ':';
+
+/// A named cluster in a [`RayClusterPool`].
+#[derive(Debug, Clone)]
+pub struct PoolCluster {
+    name: String,
+    client: RayDashboardClient,
+    labels: HashMap<String, String>,
+}
+
+impl PoolCluster {
+    /// Create a cluster entry. The name must not be empty or contain `:`, since it prefixes the
+    /// pool job IDs of the cluster's jobs.
+    pub fn new(name: impl Into<String>, client: RayDashboardClient) -> crate::Result<Self> {
+        let name = name.into();
+        if name.is_empty() || name.contains(POOL_JOB_ID_SEPARATOR) {
+            return Err(crate::Error::InvalidClusterName(name));
+        }
+        Ok(Self {
+            name,
+            client,
+            labels: HashMap::new(),
+        })
+    }
+
+    /// Add a label matched against job metadata by [`LabelMatch`].
+    pub fn with_label(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.labels.insert(key.into(), value.into());
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn client(&self) -> &RayDashboardClient {
+        &self.client
+    }
+
+    pub fn labels(&self) -> &HashMap<String, String> {
+        &self.labels
+    }
+}
+
+/// Submission ID of a job in a pool, recording the cluster the job was submitted to.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PoolJobId {
+    pub cluster: String,
+    pub submission_id: String,
+}
+
+impl PoolJobId {
+    pub fn new(cluster: impl Into<String>, submission_id: impl Into<String>) -> Self {
+        Self {
+            cluster: cluster.into(),
+            submission_id: submission_id.into(),
+        }
+    }
+}
+
+impl fmt::Display for PoolJobId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}{}{}",
+            self.cluster, POOL_JOB_ID_SEPARATOR, self.submission_id
+        )
+    }
+}
+
+impl FromStr for PoolJobId {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s.split_once(POOL_JOB_ID_SEPARATOR) {
+            Some((cluster, submission_id)) if !cluster.is_empty() && !submission_id.is_empty() => {
+                Ok(Self::new(cluster, submission_id))
+            }
+            _ => Err(crate::Error::Generic(format!(
+                "Invalid pool job ID, expected <cluster>{}<submission_id>: {}",
+                POOL_JOB_ID_SEPARATOR, s
+            ))),
+        }
+    }
+}
+
+/// Strategy picking the cluster a job is submitted to.
+#[async_trait]
+pub trait ClusterSelector: Send + Sync {
+    /// Select one of the clusters for the job. `clusters` is never empty.
+    async fn select<'a>(
+        &self,
+        clusters: &'a [PoolCluster],
+        payload: &JobSubmitRequest,
+    ) -> crate::Result<&'a PoolCluster>;
+}
+
+/// Cycle through the clusters in order.
+#[derive(Debug, Default)]
+pub struct RoundRobin {
+    next: AtomicUsize,
+}
+
+impl RoundRobin {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[async_trait]
+impl ClusterSelector for RoundRobin {
+    async fn select<'a>(
+        &self,
+        clusters: &'a [PoolCluster],
+        _payload: &JobSubmitRequest,
+    ) -> crate::Result<&'a PoolCluster> {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % clusters.len();
+        Ok(&clusters[index])
+    }
+}
+
+/// Pick the cluster with the fewest pending or running jobs, according to `list_jobs`.
+/// Clusters that cannot be reached are skipped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LeastPendingJobs;
+
+#[async_trait]
+impl ClusterSelector for LeastPendingJobs {
+    async fn select<'a>(
+        &self,
+        clusters: &'a [PoolCluster],
+        _payload: &JobSubmitRequest,
+    ) -> crate::Result<&'a PoolCluster> {
+        let counts = join_all(clusters.iter().map(|cluster| async move {
+            let jobs = cluster.client.list_jobs().await?;
+            crate::Result::Ok(jobs.iter().filter(|job| !job.status.is_terminal()).count())
+        }))
+        .await;
+
+        reachable(clusters, counts)
+            .min_by_key(|(_, count)| *count)
+            .map(|(cluster, _)| cluster)
+            .ok_or_else(|| crate::Error::NoClusterAvailable("no cluster could list its jobs".to_string()))
+    }
+}
+
+/// Pick the cluster with the most available GPUs, according to its cluster status.
+/// Clusters that cannot be reached, or cannot fit the job's entrypoint resources, are skipped.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MostFreeGpus;
+
+#[async_trait]
+impl ClusterSelector for MostFreeGpus {
+    async fn select<'a>(
+        &self,
+        clusters: &'a [PoolCluster],
+        payload: &JobSubmitRequest,
+    ) -> crate::Result<&'a PoolCluster> {
+        let statuses = join_all(clusters.iter().map(|cluster| cluster.client.get_cluster_status())).await;
+
+        reachable(clusters, statuses)
+            .filter(|(_, status)| status.resources.can_fit(payload))
+            .map(|(cluster, status)| (cluster, status.resources.available("GPU")))
+            .fold(
+                None,
+                |best: Option<(&PoolCluster, f64)>, (cluster, gpus)| match best {
+                    Some((_, best_gpus)) if best_gpus >= gpus => best,
+                    _ => Some((cluster, gpus)),
+                },
+            )
+            .map(|(cluster, _)| cluster)
+            .ok_or_else(|| {
+                crate::Error::NoClusterAvailable("no reachable cluster has enough resources".to_string())
+            })
+    }
+}
+
+/// Restrict the clusters to those whose labels match the job's metadata, then pick one of them
+/// with another selector, round-robin by default.
+///
+/// For each key, a job with that metadata key only matches clusters with the same label value.
+/// Jobs without the key match every cluster.
+pub struct LabelMatch {
+    keys: Vec<String>,
+    selector: Box<dyn ClusterSelector>,
+}
+
+impl LabelMatch {
+    pub fn new<I, K>(keys: I) -> Self
+    where
+        I: IntoIterator<Item = K>,
+        K: Into<String>,
+    {
+        Self {
+            keys: keys.into_iter().map(Into::into).collect(),
+            selector: Box::new(RoundRobin::new()),
+        }
+    }
+
+    /// Select among the matching clusters with another strategy.
+    pub fn with_selector(mut self, selector: impl ClusterSelector + 'static) -> Self {
+        self.selector = Box::new(selector);
+        self
+    }
+
+    fn matches(&self, cluster: &PoolCluster, payload: &JobSubmitRequest) -> bool {
+        let Some(ref metadata) = payload.metadata else {
+            return true;
+        };
+        self.keys.iter().all(|key| match metadata.get(key) {
+            Some(value) => cluster.labels.get(key) == Some(value),
+            None => true,
+        })
+    }
+}
+
+impl fmt::Debug for LabelMatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("LabelMatch")
+            .field("keys", &self.keys)
+            .finish_non_exhaustive()
+    }
+}
+
+#[async_trait]
+impl ClusterSelector for LabelMatch {
+    async fn select<'a>(
+        &self,
+        clusters: &'a [PoolCluster],
+        payload: &JobSubmitRequest,
+    ) -> crate::Result<&'a PoolCluster> {
+        let matching: Vec<PoolCluster> = clusters
+            .iter()
+            .filter(|cluster| self.matches(cluster, payload))
+            .cloned()
+            .collect();
+        if matching.is_empty() {
+            return Err(crate::Error::NoClusterAvailable(format!(
+                "no cluster has labels matching the job metadata {:?}",
+                payload.metadata
+            )));
+        }
+
+        let selected = self.selector.select(&matching, payload).await?;
+        let index = clusters
+            .iter()
+            .position(|cluster| cluster.name == selected.name)
+            .expect("Selected cluster is in the pool");
+        Ok(&clusters[index])
+    }
+}
+
+/// Pair each cluster with its successful result, logging and skipping failures.
+fn reachable<T>(
+    clusters: &[PoolCluster],
+    results: Vec<crate::Result<T>>,
+) -> impl Iterator<Item = (&PoolCluster, T)> {
+    clusters
+        .iter()
+        .zip(results)
+        .filter_map(|(cluster, result)| match result {
+            Ok(value) => Some((cluster, value)),
+            Err(e) => {
+                warn!("Skipping cluster {}: {}", cluster.name, e);
+                None
+            }
+        })
+}
+
+/// Named clients for several Ray clusters, submitting each job to a cluster picked by a
+/// [`ClusterSelector`].
+///
+/// Submission IDs returned by the pool, and those of the jobs it lists, are [`PoolJobId`]s
+/// in the form `<cluster>:<submission_id>`. Jobs without a submission ID, such as drivers
+/// started outside the job API, cannot be addressed through the pool and are not listed.
+/// `run_job` runs the job with the selected cluster's client, so `stop_on_cancel` applies.
+/// Clones share the same selector.
+#[derive(Clone)]
+pub struct RayClusterPool {
+    clusters: Vec<PoolCluster>,
+    selector: Arc<dyn ClusterSelector>,
+}
+
+impl fmt::Debug for RayClusterPool {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RayClusterPool")
+            .field("clusters", &self.clusters)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for RayClusterPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RayClusterPool {
+    /// Create an empty pool selecting clusters round-robin.
+    pub fn new() -> Self {
+        Self {
+            clusters: Vec::new(),
+            selector: Arc::new(RoundRobin::new()),
+        }
+    }
+
+    /// Add a cluster, replacing any cluster with the same name.
+    pub fn with_cluster(mut self, cluster: PoolCluster) -> Self {
+        match self
+            .clusters
+            .iter_mut()
+            .find(|existing| existing.name == cluster.name)
+        {
+            Some(existing) => *existing = cluster,
+            None => self.clusters.push(cluster),
+        }
+        self
+    }
+
+    pub fn with_selector(mut self, selector: impl ClusterSelector + 'static) -> Self {
+        self.selector = Arc::new(selector);
+        self
+    }
+
+    pub fn clusters(&self) -> &[PoolCluster] {
+        &self.clusters
+    }
+
+    pub fn cluster(&self, name: &str) -> Option<&PoolCluster> {
+        self.clusters.iter().find(|cluster| cluster.name == name)
+    }
+
+    /// Client of the named cluster.
+    pub fn client(&self, name: &str) -> crate::Result<&RayDashboardClient> {
+        self.cluster(name)
+            .map(PoolCluster::client)
+            .ok_or_else(|| crate::Error::UnknownCluster(name.to_string()))
+    }
+
+    /// Select the cluster a job would be submitted to.
+    pub async fn select(&self, payload: &JobSubmitRequest) -> crate::Result<&PoolCluster> {
+        if self.clusters.is_empty() {
+            return Err(crate::Error::NoClusterAvailable("the pool is empty".to_string()));
+        }
+        self.selector.select(&self.clusters, payload).await
+    }
+
+    /// Parse a pool job ID and look up the client of its cluster.
+    pub fn resolve(&self, job_id: &str) -> crate::Result<(&RayDashboardClient, PoolJobId)> {
+        let job_id: PoolJobId = job_id.parse()?;
+        Ok((self.client(&job_id.cluster)?, job_id))
+    }
+}
+
+/// Replace the submission ID of a job with its pool job ID.
+fn with_pool_id(mut details: JobDetails, cluster: &str) -> JobDetails {
+    details.submission_id = details
+        .submission_id
+        .map(|submission_id| PoolJobId::new(cluster, submission_id).to_string());
+    details
+}
+
+#[async_trait]
+impl JobSubmissionAPI for RayClusterPool {
+    #[instrument(skip_all, fields(cluster))]
+    async fn submit_job(&self, payload: &JobSubmitRequest) -> crate::Result<JobSubmitResponse> {
+        let cluster = self.select(payload).await?;
+        tracing::Span::current().record("cluster", cluster.name.as_str());
+        debug!("Submitting job to cluster {}", cluster.name);
+
+        let response = cluster.client.submit_job(payload).await?;
+        Ok(JobSubmitResponse {
+            submission_id: PoolJobId::new(&cluster.name, response.submission_id).to_string(),
+        })
+    }
+
+    /// Run the job with the client of the selected cluster, stopping it on cancellation if
+    /// `stop_on_cancel` is set. The outcome carries the pool job ID.
+    #[instrument(skip_all, fields(cluster))]
+    async fn run_job(&self, payload: &JobSubmitRequest, options: RunOptions) -> crate::Result<JobOutcome> {
+        let cluster = self.select(payload).await?;
+        tracing::Span::current().record("cluster", cluster.name.as_str());
+        debug!("Running job on cluster {}", cluster.name);
+
+        let mut outcome = cluster.client.run_job(payload, options).await?;
+        outcome.submission_id = PoolJobId::new(&cluster.name, outcome.submission_id).to_string();
+        outcome.details = with_pool_id(outcome.details, &cluster.name);
+        Ok(outcome)
+    }
+
+    /// List the jobs of every cluster, failing if any cluster cannot be reached.
+    /// Jobs without a submission ID are skipped.
+    async fn list_jobs(&self) -> crate::Result<Vec<JobDetails>> {
+        let jobs = try_join_all(self.clusters.iter().map(|cluster| async move {
+            let jobs = cluster.client.list_jobs().await?;
+            crate::Result::Ok(
+                jobs.into_iter()
+                    .filter(|job| job.submission_id.is_some())
+                    .map(|job| with_pool_id(job, &cluster.name)),
+            )
+        }))
+        .await?;
+        Ok(jobs.into_iter().flatten().collect())
+    }
+
+    async fn get_job_details(&self, submission_id: &str) -> crate::Result<JobDetails> {
+        let (client, job_id) = self.resolve(submission_id)?;
+        let details = client.get_job_details(&job_id.submission_id).await?;
+        Ok(with_pool_id(details, &job_id.cluster))
+    }
+
+    async fn delete_job(&self, submission_id: &str) -> crate::Result<JobDeleteResponse> {
+        let (client, job_id) = self.resolve(submission_id)?;
+        client.delete_job(&job_id.submission_id).await
+    }
+
+    async fn stop_job(&self, submission_id: &str) -> crate::Result<JobStopResponse> {
+        let (client, job_id) = self.resolve(submission_id)?;
+        client.stop_job(&job_id.submission_id).await
+    }
+
+    async fn get_job_logs(&self, submission_id: &str) -> crate::Result<JobLogsResponse> {
+        let (client, job_id) = self.resolve(submission_id)?;
+        client.get_job_logs(&job_id.submission_id).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_job_id_round_trip() {
+        let job_id: PoolJobId = "us-east:raysubmit_123".parse().unwrap();
+        assert_eq!(job_id, PoolJobId::new("us-east", "raysubmit_123"));
+        assert_eq!(job_id.to_string(), "us-east:raysubmit_123");
+
+        let job_id: PoolJobId = "gpu:job:with:colons".parse().unwrap();
+        assert_eq!(job_id.cluster, "gpu");
+        assert_eq!(job_id.submission_id, "job:with:colons");
+
+        assert!("raysubmit_123".parse::<PoolJobId>().is_err());
+        assert!(":raysubmit_123".parse::<PoolJobId>().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_label_match() {
+        let client = RayDashboardClient::new("http://127.0.0.1:8265").unwrap();
+        let clusters = [
+            PoolCluster::new("us-east", client.clone())
+                .unwrap()
+                .with_label("region", "us-east"),
+            PoolCluster::new("eu-west", client)
+                .unwrap()
+                .with_label("region", "eu-west"),
+        ];
+        let selector = LabelMatch::new(["region"]);
+
+        let payload = JobSubmitRequest::new("echo").with_metadata_item("region", "eu-west");
+        for _ in 0..2 {
+            let cluster = selector.select(&clusters, &payload).await.unwrap();
+            assert_eq!(cluster.name(), "eu-west");
+        }
+
+        let payload = JobSubmitRequest::new("echo").with_metadata_item("region", "ap-south");
+        assert!(selector.select(&clusters, &payload).await.is_err());
+    }
+
+    #[test]
+    fn test_cluster_name_is_validated() {
+        let client = RayDashboardClient::new("http://127.0.0.1:8265").unwrap();
+        for name in ["", "us:east"] {
+            let error = PoolCluster::new(name, client.clone()).unwrap_err();
+            assert!(matches!(error, crate::Error::InvalidClusterName(ref invalid) if invalid == name));
+        }
+    }
+}
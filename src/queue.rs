@@ -0,0 +1,549 @@
+//! Client-side queueing of job submissions.
+//!
+//! Ray accepts any number of jobs and leaves them `PENDING` until resources free up, which degrades
+//! the cluster once hundreds of jobs pile up. A [`SubmissionQueue`] holds jobs back on the client
+//! instead and only submits the next one while the jobs it tracks stay within configured limits.
+//!
+//! Jobs are tracked by a metadata tag, so jobs submitted through queues with the same tag in other
+//! processes count towards the limits as well.
+
+use std::{
+    collections::{HashMap, HashSet},
+    pin::pin,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+
+use futures::{
+    Stream,
+    channel::mpsc,
+    future::{Either, select},
+};
+use futures_timer::Delay;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, instrument, warn};
+
+use crate::{
+    client::jobs::JobSubmissionAPI,
+    schemas::{
+        jobs::{JobDetails, JobStatus, JobSubmitRequest},
+        query::JobQuery,
+    },
+};
+
+/// Metadata key holding the tag of the queue a job was submitted through.
+pub const QUEUE_METADATA_KEY: &str = "ray_dashboard_sdk_queue";
+
+/// Number of active jobs and the entrypoint resources they requested.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QueueUsage {
+    /// Jobs that are `PENDING` or `RUNNING`
+    pub active: usize,
+    pub gpus: f64,
+    pub resources: HashMap<String, f64>,
+}
+
+impl QueueUsage {
+    fn add(&mut self, gpus: Option<f64>, resources: Option<&HashMap<String, f64>>) {
+        self.active += 1;
+        self.gpus += gpus.unwrap_or_default();
+        for (resource, amount) in resources.into_iter().flatten() {
+            *self.resources.entry(resource.clone()).or_default() += amount;
+        }
+    }
+
+    fn add_job(&mut self, job: &JobDetails) {
+        self.add(job.entrypoint_num_gpus, job.entrypoint_resources.as_ref());
+    }
+
+    fn add_request(&mut self, request: &JobSubmitRequest) {
+        self.add(request.entrypoint_num_gpus, request.entrypoint_resources.as_ref());
+    }
+}
+
+/// Why the job at the head of the queue is not submitted yet.
+#[derive(Debug, Clone, PartialEq)]
+pub enum QueueBlock {
+    /// The maximum number of active jobs is reached
+    MaxActive { limit: usize },
+    /// The job's GPUs would exceed the GPU quota
+    GpuQuota { requested: f64, used: f64, quota: f64 },
+    /// The job's amount of a custom resource would exceed its quota
+    ResourceQuota {
+        resource: String,
+        requested: f64,
+        used: f64,
+        quota: f64,
+    },
+}
+
+impl std::fmt::Display for QueueBlock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            QueueBlock::MaxActive { limit } => write!(f, "{} jobs are active", limit),
+            QueueBlock::GpuQuota {
+                requested,
+                used,
+                quota,
+            } => write!(f, "{} GPUs requested, {} of {} in use", requested, used, quota),
+            QueueBlock::ResourceQuota {
+                resource,
+                requested,
+                used,
+                quota,
+            } => write!(
+                f,
+                "{} {} requested, {} of {} in use",
+                requested, resource, used, quota
+            ),
+        }
+    }
+}
+
+/// Something that happened to a job in the queue.
+#[derive(Debug, Clone)]
+pub enum QueueEvent {
+    Enqueued {
+        submission_id: String,
+        priority: i32,
+    },
+    /// The job at the head of the queue is waiting for active jobs to finish.
+    /// Emitted again only when the job or the reason changes.
+    Blocked {
+        submission_id: String,
+        reason: QueueBlock,
+    },
+    Submitted {
+        submission_id: String,
+    },
+    /// The dashboard rejected the job, which was removed from the queue.
+    /// Jobs failing to submit because the dashboard is unreachable or erroring stay queued.
+    Failed {
+        submission_id: String,
+        error: Arc<crate::Error>,
+    },
+    /// A job submitted by this queue reached a terminal state
+    Finished {
+        submission_id: String,
+        status: JobStatus,
+    },
+    /// The job was removed from the queue before it was submitted
+    Removed {
+        submission_id: String,
+    },
+}
+
+#[derive(Debug)]
+struct QueuedJob {
+    priority: i32,
+    request: JobSubmitRequest,
+}
+
+impl QueuedJob {
+    fn submission_id(&self) -> &str {
+        self.request
+            .submission_id
+            .as_deref()
+            .expect("Queued jobs have a submission ID")
+    }
+}
+
+#[derive(Debug, Default)]
+struct QueueState {
+    /// Jobs waiting to be submitted, highest priority first and in insertion order within a priority
+    pending: Vec<QueuedJob>,
+    /// Jobs submitted by this queue that have not been seen finishing yet
+    submitted: HashSet<String>,
+    /// Last [`QueueEvent::Blocked`] event, to avoid repeating it on every tick
+    blocked: Option<(String, QueueBlock)>,
+    subscribers: Vec<mpsc::UnboundedSender<QueueEvent>>,
+}
+
+/// Holds jobs on the client and submits them once active jobs are within limits.
+///
+/// Jobs are submitted strictly in priority order: a job that does not fit blocks jobs of lower
+/// priority behind it, so large jobs are not starved by a stream of small ones. Nothing is
+/// submitted until [`SubmissionQueue::tick`] or [`SubmissionQueue::run`] is called.
+#[derive(Debug)]
+pub struct SubmissionQueue {
+    tag: String,
+    max_active: Option<usize>,
+    gpu_quota: Option<f64>,
+    resource_quotas: HashMap<String, f64>,
+    state: Mutex<QueueState>,
+}
+
+impl SubmissionQueue {
+    /// Create an unlimited queue tracking jobs tagged with `tag`.
+    pub fn new(tag: impl Into<String>) -> Self {
+        Self {
+            tag: tag.into(),
+            max_active: None,
+            gpu_quota: None,
+            resource_quotas: HashMap::new(),
+            state: Mutex::new(QueueState::default()),
+        }
+    }
+
+    /// Limit the number of `PENDING` and `RUNNING` jobs. `0` means no limit.
+    pub fn with_max_active(mut self, max_active: usize) -> Self {
+        self.max_active = (max_active > 0).then_some(max_active);
+        self
+    }
+
+    /// Limit the summed `entrypoint_num_gpus` of active jobs.
+    pub fn with_gpu_quota(mut self, gpus: f64) -> Self {
+        self.gpu_quota = Some(gpus);
+        self
+    }
+
+    /// Limit the summed amount of a custom entrypoint resource of active jobs.
+    pub fn with_resource_quota(mut self, resource: impl Into<String>, amount: f64) -> Self {
+        self.resource_quotas.insert(resource.into(), amount);
+        self
+    }
+
+    pub fn tag(&self) -> &str {
+        &self.tag
+    }
+
+    /// Add a job to the queue. Jobs with a higher priority are submitted first.
+    ///
+    /// The job is tagged with [`QUEUE_METADATA_KEY`] and given a submission ID if it has none,
+    /// which is returned. Fails if the job alone exceeds a quota and could never be submitted.
+    pub fn push(&self, mut request: JobSubmitRequest, priority: i32) -> crate::Result<String> {
+        self.check_fits(&QueueUsage::default(), &request)
+            .map_err(|reason| crate::Error::ExceedsQuota(reason.to_string()))?;
+
+        let submission_id = request
+            .submission_id
+            .get_or_insert_with(|| format!("{}-{}", self.tag, uuid::Uuid::new_v4().simple()))
+            .clone();
+        request = request.with_metadata_item(QUEUE_METADATA_KEY, self.tag.as_str());
+
+        let mut state = self.state();
+        if state
+            .pending
+            .iter()
+            .any(|job| job.submission_id() == submission_id)
+        {
+            return Err(crate::Error::Generic(format!(
+                "Job {} is already queued",
+                submission_id
+            )));
+        }
+        let index = state.pending.partition_point(|job| job.priority >= priority);
+        state.pending.insert(index, QueuedJob { priority, request });
+        debug!("Queued job {} with priority {}", submission_id, priority);
+        publish(
+            &mut state,
+            &[QueueEvent::Enqueued {
+                submission_id: submission_id.clone(),
+                priority,
+            }],
+        );
+        Ok(submission_id)
+    }
+
+    /// Remove a job that was not submitted yet, returning whether it was queued.
+    pub fn remove(&self, submission_id: &str) -> bool {
+        let mut state = self.state();
+        let len = state.pending.len();
+        state.pending.retain(|job| job.submission_id() != submission_id);
+        if state.pending.len() == len {
+            return false;
+        }
+        publish(
+            &mut state,
+            &[QueueEvent::Removed {
+                submission_id: submission_id.to_string(),
+            }],
+        );
+        true
+    }
+
+    /// Submission IDs of the jobs waiting to be submitted, in the order they will be submitted.
+    pub fn pending(&self) -> Vec<String> {
+        self.state()
+            .pending
+            .iter()
+            .map(|job| job.submission_id().to_string())
+            .collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.state().pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.state().pending.is_empty()
+    }
+
+    /// Stream of all events from now on. The stream ends when the queue is dropped.
+    pub fn subscribe(&self) -> impl Stream<Item = QueueEvent> + Send + 'static {
+        let (sender, receiver) = mpsc::unbounded();
+        self.state().subscribers.push(sender);
+        receiver
+    }
+
+    /// Active jobs tagged with this queue's tag and the resources they requested.
+    pub async fn usage<C>(&self, client: &C) -> crate::Result<QueueUsage>
+    where
+        C: JobSubmissionAPI + ?Sized,
+    {
+        let mut usage = QueueUsage::default();
+        for job in self.active_jobs(client).await? {
+            usage.add_job(&job);
+        }
+        Ok(usage)
+    }
+
+    /// Submit queued jobs for as long as they fit within the limits.
+    ///
+    /// Returns the events of this tick, which are also sent to subscribers.
+    /// Fails without submitting anything if the active jobs cannot be listed. A job that fails to
+    /// submit because of a connection or server error stays at the head of the queue until the next tick.
+    pub async fn tick<C>(&self, client: &C) -> crate::Result<Vec<QueueEvent>>
+    where
+        C: JobSubmissionAPI + ?Sized,
+    {
+        let jobs = self.active_jobs(client).await?;
+
+        let mut usage = QueueUsage::default();
+        for job in &jobs {
+            usage.add_job(job);
+        }
+
+        // Jobs submitted by this queue that are no longer active have finished
+        let finished: Vec<String> = self
+            .state()
+            .submitted
+            .iter()
+            .filter(|submission_id| {
+                !jobs
+                    .iter()
+                    .any(|job| job.submission_id.as_deref() == Some(submission_id.as_str()))
+            })
+            .cloned()
+            .collect();
+        let mut events = Vec::new();
+        for submission_id in finished {
+            match client.get_job_details(&submission_id).await {
+                Ok(details) if details.status.is_terminal() => {
+                    self.state().submitted.remove(&submission_id);
+                    events.push(QueueEvent::Finished {
+                        submission_id,
+                        status: details.status,
+                    });
+                }
+                // Submitted after the listing, e.g. by a concurrent tick
+                Ok(details) => usage.add_job(&details),
+                Err(crate::Error::Request(ref e)) if e.status() == Some(reqwest::StatusCode::NOT_FOUND) => {
+                    debug!("Queued job {} was deleted", submission_id);
+                    self.state().submitted.remove(&submission_id);
+                }
+                Err(e) => warn!("Failed to get status of queued job {}: {}", submission_id, e),
+            }
+        }
+
+        loop {
+            let job = {
+                let mut state = self.state();
+                let Some(head) = state.pending.first() else {
+                    break;
+                };
+                if let Err(reason) = self.check_fits(&usage, &head.request) {
+                    let blocked = (head.submission_id().to_string(), reason);
+                    if state.blocked.as_ref() != Some(&blocked) {
+                        debug!("Job {} is blocked: {}", blocked.0, blocked.1);
+                        events.push(QueueEvent::Blocked {
+                            submission_id: blocked.0.clone(),
+                            reason: blocked.1.clone(),
+                        });
+                        state.blocked = Some(blocked);
+                    }
+                    break;
+                }
+                state.blocked = None;
+                state.pending.remove(0)
+            };
+
+            let submission_id = job.submission_id().to_string();
+            match client.submit_job(&job.request).await {
+                Ok(_) => {
+                    usage.add_request(&job.request);
+                    self.state().submitted.insert(submission_id.clone());
+                    events.push(QueueEvent::Submitted { submission_id });
+                }
+                Err(error) if is_transient(&error) => {
+                    warn!(
+                        "Failed to submit queued job {}, keeping it queued: {}",
+                        submission_id, error
+                    );
+                    let mut state = self.state();
+                    let index = state
+                        .pending
+                        .partition_point(|queued| queued.priority > job.priority);
+                    state.pending.insert(index, job);
+                    break;
+                }
+                Err(error) => events.push(QueueEvent::Failed {
+                    submission_id,
+                    error: Arc::new(error),
+                }),
+            }
+        }
+
+        publish(&mut self.state(), &events);
+        Ok(events)
+    }
+
+    /// Tick every `poll_interval` until the token is cancelled, logging every event.
+    #[instrument(skip_all, fields(tag = %self.tag))]
+    pub async fn run<C>(&self, client: &C, poll_interval: Duration, cancel: &CancellationToken)
+    where
+        C: JobSubmissionAPI + ?Sized,
+    {
+        loop {
+            match self.tick(client).await {
+                Ok(events) => events.iter().for_each(log_event),
+                Err(e) => warn!("Failed to list queued jobs: {}", e),
+            }
+
+            let cancelled = pin!(cancel.cancelled());
+            if let Either::Left(_) = select(cancelled, Delay::new(poll_interval)).await {
+                debug!("Submission queue cancelled");
+                return;
+            }
+        }
+    }
+
+    /// `PENDING` and `RUNNING` jobs tagged with this queue's tag.
+    async fn active_jobs<C>(&self, client: &C) -> crate::Result<Vec<JobDetails>>
+    where
+        C: JobSubmissionAPI + ?Sized,
+    {
+        let query = JobQuery::new()
+            .with_statuses([JobStatus::PENDING, JobStatus::RUNNING])
+            .with_metadata_item(QUEUE_METADATA_KEY, self.tag.as_str());
+        client.query_jobs(&query).await
+    }
+
+    /// Check that a request fits within the limits on top of the given usage.
+    fn check_fits(&self, usage: &QueueUsage, request: &JobSubmitRequest) -> Result<(), QueueBlock> {
+        if let Some(limit) = self.max_active
+            && usage.active >= limit
+        {
+            return Err(QueueBlock::MaxActive { limit });
+        }
+        if let Some(quota) = self.gpu_quota {
+            let requested = request.entrypoint_num_gpus.unwrap_or_default();
+            if requested > 0.0 && usage.gpus + requested > quota {
+                return Err(QueueBlock::GpuQuota {
+                    requested,
+                    used: usage.gpus,
+                    quota,
+                });
+            }
+        }
+        for (resource, &requested) in request.entrypoint_resources.iter().flatten() {
+            let Some(&quota) = self.resource_quotas.get(resource) else {
+                continue;
+            };
+            let used = usage.resources.get(resource).copied().unwrap_or_default();
+            if requested > 0.0 && used + requested > quota {
+                return Err(QueueBlock::ResourceQuota {
+                    resource: resource.clone(),
+                    requested,
+                    used,
+                    quota,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    fn state(&self) -> MutexGuard<'_, QueueState> {
+        self.state.lock().expect("Submission queue is not poisoned")
+    }
+}
+
+/// Whether a submission failed because the dashboard could not be reached or had a server error,
+/// rather than because it rejected the job.
+fn is_transient(error: &crate::Error) -> bool {
+    match error {
+        crate::Error::Request(e) => {
+            !e.is_decode() && e.status().is_none_or(|status| status.is_server_error())
+        }
+        _ => false,
+    }
+}
+
+/// Send events to all subscribers, dropping those whose stream was dropped.
+fn publish(state: &mut QueueState, events: &[QueueEvent]) {
+    state.subscribers.retain(|subscriber| {
+        events
+            .iter()
+            .all(|event| subscriber.unbounded_send(event.clone()).is_ok())
+    });
+}
+
+fn log_event(event: &QueueEvent) {
+    match event {
+        QueueEvent::Submitted { submission_id } => info!("Submitted queued job {}", submission_id),
+        QueueEvent::Failed { submission_id, error } => {
+            warn!("Failed to submit queued job {}: {}", submission_id, error)
+        }
+        QueueEvent::Finished {
+            submission_id,
+            status,
+        } => {
+            info!("Queued job {} finished with status {:?}", submission_id, status)
+        }
+        _ => debug!("{:?}", event),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_priority_order_and_quota() {
+        let queue = SubmissionQueue::new("batch").with_gpu_quota(4.0);
+        queue
+            .push(JobSubmitRequest::new("a").with_submission_id("a"), 0)
+            .unwrap();
+        queue
+            .push(JobSubmitRequest::new("b").with_submission_id("b"), 5)
+            .unwrap();
+        queue
+            .push(JobSubmitRequest::new("c").with_submission_id("c"), 0)
+            .unwrap();
+        assert_eq!(queue.pending(), ["b", "a", "c"]);
+
+        let generated = queue.push(JobSubmitRequest::new("d"), -1).unwrap();
+        assert!(generated.starts_with("batch-"));
+
+        let too_large = JobSubmitRequest::new("e").with_entrypoint_num_gpus(8.0);
+        assert!(matches!(
+            queue.push(too_large, 0),
+            Err(crate::Error::ExceedsQuota(_))
+        ));
+
+        assert!(queue.remove("a"));
+        assert!(!queue.remove("a"));
+        assert_eq!(queue.len(), 3);
+    }
+
+    #[test]
+    fn test_zero_max_active_is_unlimited() {
+        let queue = SubmissionQueue::new("batch").with_max_active(0);
+        assert!(queue.push(JobSubmitRequest::new("a"), 0).is_ok());
+        assert!(
+            queue
+                .check_fits(&QueueUsage::default(), &JobSubmitRequest::new("b"))
+                .is_ok()
+        );
+    }
+}
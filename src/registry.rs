@@ -0,0 +1,353 @@
+//! Local history of submitted jobs.
+//!
+//! Enabled with the `registry` feature. A [`JobRegistry`] keeps every job submitted by clients
+//! created with [`RayDashboardClient::with_registry`] in a JSON file, and [`JobRegistry::reconcile`]
+//! updates their status, exit code and timing from the dashboard. The history outlives Ray's own
+//! job retention and restarts of the submitting process.
+
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex, MutexGuard},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use tracing::debug;
+
+use crate::{
+    JobSubmissionAPI, RayDashboardClient,
+    schemas::{
+        jobs::{JobDetails, JobStatus, JobSubmitRequest, JobType},
+        query::JobQuery,
+    },
+};
+
+/// A job recorded in the registry.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct RegistryEntry {
+    pub submission_id: String,
+    /// Base URL of the dashboard the job was submitted to
+    pub cluster_url: String,
+    /// Request as sent to the dashboard, with the working directory replaced by its package URI
+    pub request: JobSubmitRequest,
+    pub package_uris: Vec<String>,
+    /// Milliseconds since the Unix epoch
+    pub submitted_at: u64,
+    /// Details of the job as of the last reconciliation, or as submitted if it was never reconciled
+    pub details: JobDetails,
+    /// Milliseconds since the Unix epoch, or `None` if the job was never reconciled
+    pub reconciled_at: Option<u64>,
+    /// Whether the cluster no longer knew the job when it was last reconciled. Lost jobs are
+    /// not reconciled again.
+    #[serde(default)]
+    pub lost: bool,
+}
+
+impl RegistryEntry {
+    pub(crate) fn new(cluster_url: &str, submission_id: &str, request: &JobSubmitRequest) -> Self {
+        let submitted_at = now_millis();
+        let package_uris = request
+            .runtime_env
+            .as_ref()
+            .and_then(|env| env.working_dir.as_ref())
+            .map(|uri| uri.to_string_lossy().into_owned())
+            .into_iter()
+            .collect();
+
+        let details = JobDetails {
+            job_type: JobType::SUBMISSION,
+            entrypoint: request.entrypoint.clone(),
+            status: JobStatus::PENDING,
+            submission_id: Some(submission_id.to_string()),
+            driver_info: None,
+            message: None,
+            error_type: None,
+            start_time: None,
+            end_time: None,
+            metadata: request.metadata.clone(),
+            runtime_env: request.runtime_env.clone(),
+            driver_agent_http_address: None,
+            driver_node_id: None,
+            driver_exit_code: None,
+            entrypoint_num_cpus: request.entrypoint_num_cpus,
+            entrypoint_num_gpus: request.entrypoint_num_gpus,
+            entrypoint_memory: request.entrypoint_memory,
+            entrypoint_resources: request.entrypoint_resources.clone(),
+        };
+
+        Self {
+            submission_id: submission_id.to_string(),
+            cluster_url: cluster_url.to_string(),
+            request: request.clone(),
+            package_uris,
+            submitted_at,
+            details,
+            reconciled_at: None,
+            lost: false,
+        }
+    }
+
+    pub fn status(&self) -> JobStatus {
+        self.details.status
+    }
+
+    fn is(&self, cluster_url: &str, submission_id: &str) -> bool {
+        self.cluster_url == cluster_url && self.submission_id == submission_id
+    }
+}
+
+/// Contents of a registry file.
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+struct RegistryFile {
+    jobs: Vec<RegistryEntry>,
+    /// Number of changes made since the registry was opened
+    #[serde(skip)]
+    revision: u64,
+}
+
+/// Result of reconciling the registry with a cluster.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReconcileReport {
+    /// Jobs whose details were updated
+    pub updated: Vec<String>,
+    /// Unfinished jobs the cluster no longer knows about, e.g. after it was restarted.
+    /// They are marked as lost and not reported again.
+    pub missing: Vec<String>,
+}
+
+/// Registry of submitted jobs persisted to a JSON file.
+///
+/// Every change rewrites the whole file, which suits the job volume of a single service.
+/// The file is written on Tokio's blocking thread pool and replaced atomically, so a crash never
+/// leaves it half-written.
+#[derive(Debug)]
+pub struct JobRegistry {
+    path: PathBuf,
+    file: Mutex<RegistryFile>,
+    /// Revision last written to the file, locked while writing so writes happen in order
+    written: Arc<Mutex<u64>>,
+}
+
+impl JobRegistry {
+    /// Open a registry file, starting an empty registry if it does not exist yet.
+    pub fn open(path: impl Into<PathBuf>) -> crate::Result<Self> {
+        let path = path.into();
+        let file = match std::fs::read_to_string(&path) {
+            Ok(contents) => serde_json::from_str(&contents).map_err(std::io::Error::from)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => RegistryFile::default(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path,
+            file: Mutex::new(file),
+            written: Arc::new(Mutex::new(0)),
+        })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Record a submitted job, replacing any entry with the same cluster and submission ID.
+    pub async fn record(&self, entry: RegistryEntry) -> crate::Result<()> {
+        let snapshot = {
+            let mut file = self.file();
+            file.jobs
+                .retain(|existing| !existing.is(&entry.cluster_url, &entry.submission_id));
+            debug!("Recording job {} in {}", entry.submission_id, self.path.display());
+            file.jobs.push(entry);
+            snapshot(&mut file)?
+        };
+        self.save(snapshot).await
+    }
+
+    /// All recorded jobs, in submission order.
+    pub fn entries(&self) -> Vec<RegistryEntry> {
+        self.file().jobs.clone()
+    }
+
+    /// Look up a job by the base URL of its cluster and its submission ID.
+    pub fn get(&self, cluster_url: &str, submission_id: &str) -> Option<RegistryEntry> {
+        self.file()
+            .jobs
+            .iter()
+            .find(|entry| entry.is(cluster_url, submission_id))
+            .cloned()
+    }
+
+    /// Recorded jobs whose details match a query, sorted and paginated by it.
+    pub fn query(&self, query: &JobQuery) -> Vec<RegistryEntry> {
+        query.apply_by(self.entries(), |entry| &entry.details)
+    }
+
+    /// Remove a job from the registry, returning whether it was recorded.
+    pub async fn remove(&self, cluster_url: &str, submission_id: &str) -> crate::Result<bool> {
+        let snapshot = {
+            let mut file = self.file();
+            let len = file.jobs.len();
+            file.jobs.retain(|entry| !entry.is(cluster_url, submission_id));
+            if file.jobs.len() == len {
+                return Ok(false);
+            }
+            snapshot(&mut file)?
+        };
+        self.save(snapshot).await?;
+        Ok(true)
+    }
+
+    /// Update the unfinished jobs submitted to the client's cluster with their current details,
+    /// and mark those the cluster no longer knows as lost.
+    ///
+    /// The cluster's jobs are listed once and matched to the registry by submission ID.
+    pub async fn reconcile(&self, client: &RayDashboardClient) -> crate::Result<ReconcileReport> {
+        let cluster_url = client.base_url().as_str();
+        let mut jobs: HashMap<String, JobDetails> = client
+            .list_jobs()
+            .await?
+            .into_iter()
+            .filter_map(|job| Some((job.submission_id.clone()?, job)))
+            .collect();
+
+        let mut report = ReconcileReport::default();
+        let reconciled_at = now_millis();
+        let snapshot = {
+            let mut file = self.file();
+            let unfinished = file.jobs.iter_mut().filter(|entry| {
+                entry.cluster_url == cluster_url && !entry.lost && !entry.status().is_terminal()
+            });
+            for entry in unfinished {
+                match jobs.remove(&entry.submission_id) {
+                    Some(details) => {
+                        entry.details = details;
+                        report.updated.push(entry.submission_id.clone());
+                    }
+                    None => {
+                        entry.lost = true;
+                        report.missing.push(entry.submission_id.clone());
+                    }
+                }
+                entry.reconciled_at = Some(reconciled_at);
+            }
+            if report.updated.is_empty() && report.missing.is_empty() {
+                return Ok(report);
+            }
+            snapshot(&mut file)?
+        };
+        self.save(snapshot).await?;
+        Ok(report)
+    }
+
+    fn file(&self) -> MutexGuard<'_, RegistryFile> {
+        self.file.lock().expect("Job registry is not poisoned")
+    }
+
+    /// Write a snapshot to a temporary file and move it over the registry file, unless a later
+    /// snapshot was written first.
+    async fn save(&self, (revision, contents): (u64, String)) -> crate::Result<()> {
+        let path = self.path.clone();
+        let written = self.written.clone();
+        tokio::task::spawn_blocking(move || {
+            let mut written = written.lock().expect("Job registry writer is not poisoned");
+            if *written >= revision {
+                return Ok(());
+            }
+            let mut temp_path = path.clone().into_os_string();
+            temp_path.push(".tmp");
+            std::fs::write(&temp_path, contents)?;
+            std::fs::rename(&temp_path, &path)?;
+            *written = revision;
+            Ok(())
+        })
+        .await
+        .map_err(std::io::Error::from)?
+    }
+}
+
+/// Serialize the registry after a change, along with the revision of the change.
+fn snapshot(file: &mut RegistryFile) -> crate::Result<(u64, String)> {
+    file.revision += 1;
+    let contents = serde_json::to_string_pretty(file).map_err(std::io::Error::from)?;
+    Ok((file.revision, contents))
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_registry_persists_and_queries() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("jobs.json");
+        let cluster_url = "http://127.0.0.1:8265/";
+
+        let registry = JobRegistry::open(&path).unwrap();
+        let request = JobSubmitRequest::new("python train.py").with_metadata_item("team", "ml");
+        registry
+            .record(RegistryEntry::new(cluster_url, "job-1", &request))
+            .await
+            .unwrap();
+        registry
+            .record(RegistryEntry::new(
+                cluster_url,
+                "job-2",
+                &JobSubmitRequest::new("echo"),
+            ))
+            .await
+            .unwrap();
+        registry
+            .record(RegistryEntry::new(
+                "http://10.0.0.2:8265/",
+                "job-1",
+                &JobSubmitRequest::new("echo"),
+            ))
+            .await
+            .unwrap();
+
+        let registry = JobRegistry::open(&path).unwrap();
+        assert_eq!(registry.entries().len(), 3);
+        let entry = registry.get(cluster_url, "job-1").unwrap();
+        assert_eq!(entry.request.entrypoint, "python train.py");
+        assert_eq!(entry.details.start_time, None);
+
+        let query = JobQuery::new().with_metadata_item("team", "ml");
+        let entries = registry.query(&query);
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].cluster_url, cluster_url);
+        assert_eq!(entries[0].submission_id, "job-1");
+
+        assert!(registry.remove(cluster_url, "job-2").await.unwrap());
+        assert!(!registry.remove(cluster_url, "job-2").await.unwrap());
+        assert!(registry.remove("http://10.0.0.2:8265/", "job-1").await.unwrap());
+        let registry = JobRegistry::open(&path).unwrap();
+        assert_eq!(registry.entries().len(), 1);
+        assert!(registry.get(cluster_url, "job-1").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_registry_concurrent_records_are_all_persisted() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("jobs.json");
+        let registry = JobRegistry::open(&path).unwrap();
+
+        let records = (0..20).map(|i| {
+            let entry = RegistryEntry::new(
+                "http://127.0.0.1:8265/",
+                &format!("job-{i}"),
+                &JobSubmitRequest::new("echo"),
+            );
+            registry.record(entry)
+        });
+        for result in futures::future::join_all(records).await {
+            result.unwrap();
+        }
+
+        assert_eq!(JobRegistry::open(&path).unwrap().entries().len(), 20);
+    }
+}
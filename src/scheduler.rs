@@ -0,0 +1,421 @@
+//! Submitting jobs on a schedule.
+//!
+//! Enabled with the `scheduler` feature. A [`Scheduler`] submits copies of [`JobSubmitRequest`]
+//! templates on cron expressions or fixed intervals, with an [`OverlapPolicy`] deciding what
+//! happens when the previous run of a schedule is still running.
+
+use std::{pin::pin, str::FromStr, time::Duration};
+
+use chrono::{DateTime, Utc};
+use futures::future::{Either, select};
+use futures_timer::Delay;
+use tokio_util::sync::CancellationToken;
+use tracing::{debug, info, instrument, warn};
+
+use crate::{
+    client::jobs::JobSubmissionAPI,
+    schemas::jobs::{JobStatus, JobSubmitRequest},
+};
+
+/// Submission ID template used unless a schedule sets its own.
+pub const DEFAULT_SUBMISSION_ID_TEMPLATE: &str = "{name}-{time}";
+
+/// When a schedule fires.
+#[derive(Debug, Clone)]
+pub enum Trigger {
+    /// A cron expression with a seconds field, e.g. `0 0 2 * * *` for 2am UTC every day
+    Cron(Box<cron::Schedule>),
+    /// A fixed interval, starting one interval after the scheduler first sees the schedule
+    Interval(Duration),
+}
+
+impl Trigger {
+    /// Parse a cron expression. Times are in UTC.
+    pub fn cron(expression: &str) -> crate::Result<Self> {
+        let schedule = cron::Schedule::from_str(expression)
+            .map_err(|e| crate::Error::Generic(format!("Invalid cron expression {}: {}", expression, e)))?;
+        Ok(Trigger::Cron(Box::new(schedule)))
+    }
+
+    pub fn interval(interval: Duration) -> Self {
+        Trigger::Interval(interval)
+    }
+
+    /// First time the trigger fires strictly after `time`, or `None` if it never fires again.
+    pub fn next_after(&self, time: DateTime<Utc>) -> Option<DateTime<Utc>> {
+        match self {
+            Trigger::Cron(schedule) => schedule.after(&time).next(),
+            Trigger::Interval(interval) => {
+                let interval = chrono::Duration::from_std(*interval).ok()?;
+                (!interval.is_zero()).then(|| time + interval)
+            }
+        }
+    }
+}
+
+/// What to do when a schedule fires while its previous run is still pending or running.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OverlapPolicy {
+    /// Do not submit this run
+    #[default]
+    Skip,
+    /// Submit this run once the previous one has finished. At most one run waits, so runs
+    /// firing while another is queued are skipped
+    Queue,
+    /// Stop the previous run and submit this one
+    Replace,
+}
+
+/// A job template submitted on a trigger.
+#[derive(Debug, Clone)]
+pub struct ScheduledJob {
+    name: String,
+    trigger: Trigger,
+    template: JobSubmitRequest,
+    overlap: OverlapPolicy,
+    submission_id_template: String,
+}
+
+impl ScheduledJob {
+    /// Create a schedule. The submission ID of the template is replaced on every run.
+    pub fn new(name: impl Into<String>, trigger: Trigger, template: JobSubmitRequest) -> Self {
+        Self {
+            name: name.into(),
+            trigger,
+            template,
+            overlap: OverlapPolicy::default(),
+            submission_id_template: DEFAULT_SUBMISSION_ID_TEMPLATE.to_string(),
+        }
+    }
+
+    pub fn with_overlap_policy(mut self, overlap: OverlapPolicy) -> Self {
+        self.overlap = overlap;
+        self
+    }
+
+    /// Set the template for submission IDs. `{name}` is replaced by the schedule name,
+    /// `{time}` by the scheduled time as `%Y%m%dT%H%M%SZ` and `{timestamp}` by the
+    /// scheduled time in seconds since the Unix epoch.
+    pub fn with_submission_id_template(mut self, template: impl Into<String>) -> Self {
+        self.submission_id_template = template.into();
+        self
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Submission ID of the run scheduled at `time`.
+    pub fn submission_id(&self, time: DateTime<Utc>) -> String {
+        self.submission_id_template
+            .replace("{name}", &self.name)
+            .replace("{time}", &time.format("%Y%m%dT%H%M%SZ").to_string())
+            .replace("{timestamp}", &time.timestamp().to_string())
+    }
+
+    fn payload(&self, time: DateTime<Utc>) -> JobSubmitRequest {
+        let mut payload = self.template.clone();
+        payload.submission_id = Some(self.submission_id(time));
+        payload
+    }
+}
+
+/// Something the scheduler did when a schedule fired.
+#[derive(Debug)]
+pub enum ScheduleEvent {
+    Submitted {
+        name: String,
+        scheduled_at: DateTime<Utc>,
+        submission_id: String,
+    },
+    /// The run was not submitted because the previous run is still active, or another run is queued
+    Skipped {
+        name: String,
+        scheduled_at: DateTime<Utc>,
+        active: String,
+    },
+    /// The run will be submitted once the previous run has finished
+    Queued {
+        name: String,
+        scheduled_at: DateTime<Utc>,
+        active: String,
+    },
+    /// The previous run was stopped and this run submitted
+    Replaced {
+        name: String,
+        scheduled_at: DateTime<Utc>,
+        stopped: String,
+        submission_id: String,
+    },
+    Failed {
+        name: String,
+        scheduled_at: DateTime<Utc>,
+        error: crate::Error,
+    },
+}
+
+#[derive(Debug)]
+struct ScheduleState {
+    job: ScheduledJob,
+    next_run: Option<DateTime<Utc>>,
+    /// Submission ID of the last submitted run
+    last_submission_id: Option<String>,
+    /// Scheduled time of the run waiting for the previous run to finish
+    queued: Option<DateTime<Utc>>,
+}
+
+/// Submits scheduled jobs while [`Scheduler::run`] is running.
+///
+/// Runs missed while the scheduler was not ticking, e.g. because a tick took long,
+/// are collapsed into a single run.
+#[derive(Debug, Default)]
+pub struct Scheduler {
+    schedules: Vec<ScheduleState>,
+}
+
+impl Scheduler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_job(mut self, job: ScheduledJob) -> Self {
+        self.schedules.push(ScheduleState {
+            job,
+            next_run: None,
+            last_submission_id: None,
+            queued: None,
+        });
+        self
+    }
+
+    /// Next time any schedule fires.
+    pub fn next_run(&self) -> Option<DateTime<Utc>> {
+        self.schedules
+            .iter()
+            .filter_map(|schedule| schedule.next_run)
+            .min()
+    }
+
+    /// Submit the runs that are due at `now`, and queued runs whose previous run has finished.
+    ///
+    /// Schedules seen for the first time are only planned, firing at their next trigger time after `now`.
+    pub async fn tick<C>(&mut self, client: &C, now: DateTime<Utc>) -> Vec<ScheduleEvent>
+    where
+        C: JobSubmissionAPI + ?Sized,
+    {
+        let mut events = Vec::new();
+        for schedule in &mut self.schedules {
+            if let Some(scheduled_at) = schedule.queued
+                && !is_active(client, schedule.last_submission_id.as_deref()).await
+            {
+                schedule.queued = None;
+                events.push(submit(client, schedule, scheduled_at).await);
+            }
+
+            let Some(next_run) = schedule.next_run else {
+                schedule.next_run = schedule.job.trigger.next_after(now);
+                continue;
+            };
+            if next_run > now {
+                continue;
+            }
+            schedule.next_run = schedule.job.trigger.next_after(now);
+            events.push(fire(client, schedule, next_run).await);
+        }
+        events
+    }
+
+    /// Tick whenever a schedule is due until the token is cancelled, logging every event.
+    ///
+    /// Queued runs are checked at most every `poll_interval`.
+    #[instrument(skip_all)]
+    pub async fn run<C>(&mut self, client: &C, poll_interval: Duration, cancel: &CancellationToken)
+    where
+        C: JobSubmissionAPI + ?Sized,
+    {
+        loop {
+            for event in self.tick(client, Utc::now()).await {
+                log_event(&event);
+            }
+
+            let mut delay = poll_interval;
+            if let Some(next_run) = self.next_run() {
+                delay = delay.min((next_run - Utc::now()).to_std().unwrap_or_default());
+            }
+
+            let cancelled = pin!(cancel.cancelled());
+            if let Either::Left(_) = select(cancelled, Delay::new(delay)).await {
+                debug!("Scheduler cancelled");
+                return;
+            }
+        }
+    }
+}
+
+/// Whether a run is still pending or running. Runs that cannot be looked up count as finished.
+async fn is_active<C>(client: &C, submission_id: Option<&str>) -> bool
+where
+    C: JobSubmissionAPI + ?Sized,
+{
+    let Some(submission_id) = submission_id else {
+        return false;
+    };
+    match client.get_job_status(submission_id).await {
+        Ok(status) => matches!(status, JobStatus::PENDING | JobStatus::RUNNING),
+        Err(e) => {
+            warn!(
+                "Failed to get status of job {}, assuming it finished: {}",
+                submission_id, e
+            );
+            false
+        }
+    }
+}
+
+async fn fire<C>(client: &C, schedule: &mut ScheduleState, scheduled_at: DateTime<Utc>) -> ScheduleEvent
+where
+    C: JobSubmissionAPI + ?Sized,
+{
+    let active = match schedule.last_submission_id.clone() {
+        // The queued run goes first, even if the previous run finished since it was checked
+        Some(submission_id) if schedule.queued.is_some() => submission_id,
+        Some(submission_id) if is_active(client, Some(&submission_id)).await => submission_id,
+        _ => return submit(client, schedule, scheduled_at).await,
+    };
+
+    let name = schedule.job.name.clone();
+    match schedule.job.overlap {
+        OverlapPolicy::Skip => ScheduleEvent::Skipped {
+            name,
+            scheduled_at,
+            active,
+        },
+        OverlapPolicy::Queue if schedule.queued.is_some() => ScheduleEvent::Skipped {
+            name,
+            scheduled_at,
+            active,
+        },
+        OverlapPolicy::Queue => {
+            schedule.queued = Some(scheduled_at);
+            ScheduleEvent::Queued {
+                name,
+                scheduled_at,
+                active,
+            }
+        }
+        OverlapPolicy::Replace => {
+            if let Err(error) = client.stop_job(&active).await {
+                return ScheduleEvent::Failed {
+                    name,
+                    scheduled_at,
+                    error,
+                };
+            }
+            match submit(client, schedule, scheduled_at).await {
+                ScheduleEvent::Submitted { submission_id, .. } => ScheduleEvent::Replaced {
+                    name,
+                    scheduled_at,
+                    stopped: active,
+                    submission_id,
+                },
+                event => event,
+            }
+        }
+    }
+}
+
+async fn submit<C>(client: &C, schedule: &mut ScheduleState, scheduled_at: DateTime<Utc>) -> ScheduleEvent
+where
+    C: JobSubmissionAPI + ?Sized,
+{
+    let name = schedule.job.name.clone();
+    match client.submit_job(&schedule.job.payload(scheduled_at)).await {
+        Ok(response) => {
+            schedule.last_submission_id = Some(response.submission_id.clone());
+            ScheduleEvent::Submitted {
+                name,
+                scheduled_at,
+                submission_id: response.submission_id,
+            }
+        }
+        Err(error) => ScheduleEvent::Failed {
+            name,
+            scheduled_at,
+            error,
+        },
+    }
+}
+
+fn log_event(event: &ScheduleEvent) {
+    match event {
+        ScheduleEvent::Submitted {
+            name, submission_id, ..
+        } => info!("Submitted scheduled job {} as {}", name, submission_id),
+        ScheduleEvent::Skipped { name, active, .. } => {
+            info!(
+                "Skipped scheduled job {} because {} is still active",
+                name, active
+            )
+        }
+        ScheduleEvent::Queued { name, active, .. } => {
+            info!("Queued scheduled job {} until {} finishes", name, active)
+        }
+        ScheduleEvent::Replaced {
+            name,
+            stopped,
+            submission_id,
+            ..
+        } => info!(
+            "Stopped {} and submitted scheduled job {} as {}",
+            stopped, name, submission_id
+        ),
+        ScheduleEvent::Failed { name, error, .. } => {
+            warn!("Failed to submit scheduled job {}: {}", name, error)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn time(value: &str) -> DateTime<Utc> {
+        value.parse().unwrap()
+    }
+
+    #[test]
+    fn test_trigger_next_after() {
+        let nightly = Trigger::cron("0 0 2 * * *").unwrap();
+        assert_eq!(
+            nightly.next_after(time("2025-06-01T12:00:00Z")),
+            Some(time("2025-06-02T02:00:00Z"))
+        );
+
+        let hourly = Trigger::interval(Duration::from_secs(3600));
+        assert_eq!(
+            hourly.next_after(time("2025-06-01T12:00:00Z")),
+            Some(time("2025-06-01T13:00:00Z"))
+        );
+
+        assert!(Trigger::cron("not a cron expression").is_err());
+    }
+
+    #[test]
+    fn test_submission_id_template() {
+        let job = ScheduledJob::new(
+            "nightly",
+            Trigger::interval(Duration::from_secs(60)),
+            JobSubmitRequest::new("python batch.py"),
+        );
+        assert_eq!(
+            job.submission_id(time("2025-06-01T02:00:00Z")),
+            "nightly-20250601T020000Z"
+        );
+
+        let job = job.with_submission_id_template("batch-{timestamp}");
+        assert_eq!(
+            job.submission_id(time("2025-06-01T02:00:00Z")),
+            "batch-1748743200"
+        );
+    }
+}
@@ -0,0 +1,162 @@
+use std::fmt;
+
+/// A parsed Ray version, e.g. `2.50.1`.
+/// Pre-release suffixes such as `.dev0` or `rc1` are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct RayVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl RayVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self { major, minor, patch }
+    }
+
+    /// Parse a version string reported by the dashboard.
+    pub fn parse(version: &str) -> crate::Result<Self> {
+        let mut parts = version.trim().split('.').map(|part| {
+            let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse::<u32>().ok()
+        });
+
+        let mut next = || parts.next().flatten();
+        match (next(), next(), next()) {
+            (Some(major), Some(minor), patch) => Ok(Self::new(major, minor, patch.unwrap_or(0))),
+            _ => Err(crate::Error::Generic(format!("Invalid Ray version: {}", version))),
+        }
+    }
+}
+
+impl fmt::Display for RayVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// Server features that are not available on every supported Ray version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ServerFeature {
+    /// `uv` packages in the runtime environment, added in Ray 2.37.0
+    UvRuntimeEnv,
+    /// Streaming job logs via `/api/jobs/{id}/logs/tail`, required from Ray 2.0.0
+    JobLogsTail,
+    /// State API (`/api/v0/...`) for logs and job listings with filters, added in Ray 2.0.0
+    StateApi,
+    /// Serve REST API (`/api/serve/applications/`) for multi-application configs, added in Ray 2.4.0
+    ServeV2Api,
+}
+
+impl ServerFeature {
+    /// First Ray version supporting the feature.
+    pub fn min_version(&self) -> RayVersion {
+        match self {
+            ServerFeature::UvRuntimeEnv => RayVersion::new(2, 37, 0),
+            ServerFeature::JobLogsTail => RayVersion::new(2, 0, 0),
+            ServerFeature::StateApi => RayVersion::new(2, 0, 0),
+            ServerFeature::ServeV2Api => RayVersion::new(2, 4, 0),
+        }
+    }
+}
+
+impl fmt::Display for ServerFeature {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ServerFeature::UvRuntimeEnv => "uv runtime env",
+            ServerFeature::JobLogsTail => "job logs tail",
+            ServerFeature::StateApi => "state API",
+            ServerFeature::ServeV2Api => "serve v2 API",
+        };
+        f.write_str(name)
+    }
+}
+
+/// Features supported by the connected Ray cluster.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ServerCapabilities {
+    pub version: RayVersion,
+    pub uv_runtime_env: bool,
+    pub job_logs_tail: bool,
+    pub state_api: bool,
+    pub serve_v2_api: bool,
+}
+
+impl ServerCapabilities {
+    /// Determine the capabilities of a server from its version.
+    pub fn from_version(version: RayVersion) -> Self {
+        let supports = |feature: ServerFeature| version >= feature.min_version();
+        Self {
+            version,
+            uv_runtime_env: supports(ServerFeature::UvRuntimeEnv),
+            job_logs_tail: supports(ServerFeature::JobLogsTail),
+            state_api: supports(ServerFeature::StateApi),
+            serve_v2_api: supports(ServerFeature::ServeV2Api),
+        }
+    }
+
+    /// Check if the server supports a feature.
+    pub fn supports(&self, feature: ServerFeature) -> bool {
+        match feature {
+            ServerFeature::UvRuntimeEnv => self.uv_runtime_env,
+            ServerFeature::JobLogsTail => self.job_logs_tail,
+            ServerFeature::StateApi => self.state_api,
+            ServerFeature::ServeV2Api => self.serve_v2_api,
+        }
+    }
+
+    /// Return an error if the server does not support a feature.
+    pub fn require(&self, feature: ServerFeature) -> crate::Result<()> {
+        if self.supports(feature) {
+            return Ok(());
+        }
+        Err(crate::Error::Unsupported {
+            feature: feature.to_string(),
+            server_version: self.version.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_version() {
+        assert_eq!(RayVersion::parse("2.50.1").unwrap(), RayVersion::new(2, 50, 1));
+        assert_eq!(RayVersion::parse("3.0.0.dev0").unwrap(), RayVersion::new(3, 0, 0));
+        assert_eq!(RayVersion::parse("2.9").unwrap(), RayVersion::new(2, 9, 0));
+        assert!(RayVersion::parse("nightly").is_err());
+    }
+
+    #[test]
+    fn test_version_ordering() {
+        assert!(RayVersion::new(2, 50, 0) > RayVersion::new(2, 9, 3));
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let capabilities = ServerCapabilities::from_version(RayVersion::new(2, 9, 0));
+        assert!(capabilities.state_api);
+        assert!(capabilities.job_logs_tail);
+        assert!(capabilities.serve_v2_api);
+        assert!(!capabilities.uv_runtime_env);
+
+        let legacy = ServerCapabilities::from_version(RayVersion::new(1, 13, 0));
+        assert!(!legacy.state_api);
+        assert!(!legacy.job_logs_tail);
+        assert!(!legacy.serve_v2_api);
+        let before_serve_v2 = ServerCapabilities::from_version(RayVersion::new(2, 3, 1));
+        assert!(before_serve_v2.supports(ServerFeature::JobLogsTail));
+        assert!(!before_serve_v2.supports(ServerFeature::ServeV2Api));
+        assert!(
+            ServerCapabilities::from_version(RayVersion::new(2, 4, 0)).supports(ServerFeature::ServeV2Api)
+        );
+
+        let error = capabilities.require(ServerFeature::UvRuntimeEnv).unwrap_err();
+        assert!(matches!(
+            error,
+            crate::Error::Unsupported { ref server_version, .. } if server_version == "2.9.0"
+        ));
+    }
+}
@@ -0,0 +1,274 @@
+// Reference: https://github.com/ray-project/ray/blob/master/python/ray/dashboard/modules/reporter/reporter_head.py
+//
+// The dashboard converts every key of the cluster status to camelCase, including resource names,
+// so custom resources such as `my_accelerator` are reported as `myAccelerator`.
+
+use std::collections::HashMap;
+
+use crate::schemas::jobs::JobSubmitRequest;
+
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClusterStatusData {
+    pub autoscaling_status: Option<String>,
+    pub autoscaling_error: Option<String>,
+    pub cluster_status: Option<RawClusterStatus>,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RawClusterStatus {
+    #[serde(default)]
+    pub autoscaler_report: Option<AutoscalerReport>,
+    #[serde(default)]
+    pub load_metrics_report: Option<LoadMetricsReport>,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AutoscalerReport {
+    #[serde(default)]
+    pub active_nodes: HashMap<String, u64>,
+    /// Entries of `[ip, node_type, status]`
+    #[serde(default)]
+    pub pending_nodes: Vec<Vec<serde_json::Value>>,
+    #[serde(default)]
+    pub pending_launches: HashMap<String, u64>,
+    /// Entries of `[ip, node_type]`
+    #[serde(default)]
+    pub failed_nodes: Vec<Vec<serde_json::Value>>,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoadMetricsReport {
+    /// Resource name to `[used, total]`
+    #[serde(default)]
+    pub usage: HashMap<String, (f64, f64)>,
+    /// Entries of `[resource shape, count]`
+    #[serde(default)]
+    pub resource_demand: Vec<(HashMap<String, f64>, u64)>,
+}
+
+/// Total and available amount of each resource in the cluster.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ClusterResources {
+    pub total: HashMap<String, f64>,
+    pub available: HashMap<String, f64>,
+}
+
+impl ClusterResources {
+    /// Total amount of a resource, or zero if the cluster does not have it.
+    pub fn total(&self, resource: &str) -> f64 {
+        lookup(&self.total, resource)
+    }
+
+    /// Available amount of a resource, or zero if the cluster does not have it.
+    pub fn available(&self, resource: &str) -> f64 {
+        lookup(&self.available, resource)
+    }
+
+    /// Check if the available resources can satisfy the entrypoint resources of a job.
+    ///
+    /// This compares against resources available across the whole cluster, so a job may still be unschedulable
+    /// if no single node has enough free capacity.
+    pub fn can_fit(&self, request: &JobSubmitRequest) -> bool {
+        let mut required: Vec<(&str, f64)> = Vec::new();
+        if let Some(cpus) = request.entrypoint_num_cpus {
+            required.push(("CPU", cpus));
+        }
+        if let Some(gpus) = request.entrypoint_num_gpus {
+            required.push(("GPU", gpus));
+        }
+        if let Some(memory) = request.entrypoint_memory {
+            required.push(("memory", memory as f64));
+        }
+        if let Some(ref resources) = request.entrypoint_resources {
+            required.extend(resources.iter().map(|(name, amount)| (name.as_str(), *amount)));
+        }
+
+        required
+            .into_iter()
+            .all(|(resource, amount)| amount <= 0.0 || self.available(resource) >= amount)
+    }
+}
+
+/// A node the autoscaler is launching.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingNode {
+    pub ip: Option<String>,
+    pub node_type: String,
+    pub status: Option<String>,
+}
+
+/// A node the autoscaler failed to launch or that died.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailedNode {
+    pub ip: Option<String>,
+    pub node_type: String,
+}
+
+/// Resources requested by pending work that the cluster cannot currently satisfy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ResourceDemand {
+    pub shape: HashMap<String, f64>,
+    pub count: u64,
+}
+
+/// Status of the cluster as reported by the autoscaler.
+#[derive(Debug, Clone, Default)]
+pub struct ClusterStatus {
+    pub autoscaling_status: Option<String>,
+    pub autoscaling_error: Option<String>,
+    pub resources: ClusterResources,
+    /// Number of active nodes per node type
+    pub active_nodes: HashMap<String, u64>,
+    pub pending_nodes: Vec<PendingNode>,
+    /// Number of nodes being requested per node type
+    pub pending_launches: HashMap<String, u64>,
+    pub failed_nodes: Vec<FailedNode>,
+    pub resource_demand: Vec<ResourceDemand>,
+}
+
+impl From<ClusterStatusData> for ClusterStatus {
+    fn from(data: ClusterStatusData) -> Self {
+        let raw = data.cluster_status.unwrap_or_default();
+        let autoscaler = raw.autoscaler_report.unwrap_or_default();
+        let load = raw.load_metrics_report.unwrap_or_default();
+
+        let mut resources = ClusterResources::default();
+        for (name, (used, total)) in load.usage {
+            resources.available.insert(name.clone(), (total - used).max(0.0));
+            resources.total.insert(name, total);
+        }
+
+        let pending_nodes = autoscaler
+            .pending_nodes
+            .iter()
+            .map(|entry| PendingNode {
+                ip: string_at(entry, 0),
+                node_type: string_at(entry, 1).unwrap_or_default(),
+                status: string_at(entry, 2),
+            })
+            .collect();
+
+        let failed_nodes = autoscaler
+            .failed_nodes
+            .iter()
+            .map(|entry| FailedNode {
+                ip: string_at(entry, 0),
+                node_type: string_at(entry, 1).unwrap_or_default(),
+            })
+            .collect();
+
+        let resource_demand = load
+            .resource_demand
+            .into_iter()
+            .map(|(shape, count)| ResourceDemand { shape, count })
+            .collect();
+
+        Self {
+            autoscaling_status: data.autoscaling_status,
+            autoscaling_error: data.autoscaling_error,
+            resources,
+            active_nodes: autoscaler.active_nodes,
+            pending_nodes,
+            pending_launches: autoscaler.pending_launches,
+            failed_nodes,
+            resource_demand,
+        }
+    }
+}
+
+/// Look up a resource by name, falling back to the camelCase name reported by the dashboard.
+fn lookup(resources: &HashMap<String, f64>, resource: &str) -> f64 {
+    resources
+        .get(resource)
+        .or_else(|| resources.get(&to_camel_case(resource)))
+        .copied()
+        .unwrap_or(0.0)
+}
+
+/// Mirror of the key conversion applied by the dashboard to its responses.
+fn to_camel_case(name: &str) -> String {
+    let mut components = name.split('_');
+    let mut camel = components.next().unwrap_or_default().to_string();
+    for component in components {
+        let mut chars = component.chars();
+        if let Some(first) = chars.next() {
+            camel.extend(first.to_uppercase());
+            camel.push_str(&chars.as_str().to_lowercase());
+        }
+    }
+    camel
+}
+
+fn string_at(entry: &[serde_json::Value], index: usize) -> Option<String> {
+    entry
+        .get(index)
+        .and_then(|value| value.as_str())
+        .map(String::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cluster_status() -> ClusterStatus {
+        let json = serde_json::json!({
+            "autoscalingStatus": "Autoscaler status",
+            "autoscalingError": null,
+            "clusterStatus": {
+                "autoscalerReport": {
+                    "activeNodes": {"head": 1},
+                    "pendingNodes": [["10.0.0.2", "gpu_worker", "setting-up"]],
+                    "pendingLaunches": {"gpu_worker": 2},
+                    "failedNodes": [["10.0.0.3", "gpu_worker"]],
+                },
+                "loadMetricsReport": {
+                    "usage": {
+                        "CPU": [2.0, 8.0],
+                        "GPU": [1.0, 1.0],
+                        "memory": [0.0, 1024.0],
+                        "myAccelerator": [0.0, 4.0],
+                    },
+                    "resourceDemand": [[{"GPU": 1.0}, 3]],
+                },
+            },
+        });
+        let data: ClusterStatusData = serde_json::from_value(json).unwrap();
+        data.into()
+    }
+
+    #[test]
+    fn test_cluster_status_conversion() {
+        let status = cluster_status();
+        assert_eq!(status.resources.total("CPU"), 8.0);
+        assert_eq!(status.resources.available("CPU"), 6.0);
+        assert_eq!(status.resources.available("GPU"), 0.0);
+        assert_eq!(status.active_nodes.get("head"), Some(&1));
+        assert_eq!(status.pending_nodes[0].node_type, "gpu_worker");
+        assert_eq!(status.failed_nodes[0].ip.as_deref(), Some("10.0.0.3"));
+        assert_eq!(status.resource_demand[0].count, 3);
+    }
+
+    #[test]
+    fn test_can_fit() {
+        let resources = cluster_status().resources;
+
+        let request = JobSubmitRequest::new("python train.py")
+            .with_entrypoint_num_cpus(4.0)
+            .with_entrypoint_memory(512)
+            .with_entrypoint_resources(HashMap::from([("my_accelerator".to_string(), 2.0)]));
+        assert!(resources.can_fit(&request));
+
+        let request = JobSubmitRequest::new("python train.py").with_entrypoint_num_gpus(1.0);
+        assert!(!resources.can_fit(&request));
+    }
+
+    #[test]
+    fn test_to_camel_case() {
+        assert_eq!(to_camel_case("object_store_memory"), "objectStoreMemory");
+        assert_eq!(to_camel_case("CPU"), "CPU");
+    }
+}
@@ -4,3 +4,87 @@ pub struct RayVersionResponse {
     pub ray_version: String,
     pub ray_commit: String,
 }
+
+/// Envelope returned by dashboard endpoints such as the state API and cluster status.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct DashboardResponse<T> {
+    pub result: bool,
+    pub msg: String,
+    pub data: T,
+}
+
+/// Result of a state API list endpoint such as `/api/v0/jobs`.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct StateListData<T> {
+    pub result: StateListResult<T>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct StateListResult<T> {
+    /// Number of entries before filtering and truncation
+    pub total: usize,
+    /// Number of entries after truncation at the data source, before filtering
+    pub num_after_truncation: usize,
+    /// Number of entries after filtering, before they were truncated to the requested limit
+    #[serde(default)]
+    pub num_filtered: Option<usize>,
+    pub result: Vec<T>,
+}
+
+impl<T> StateListResult<T> {
+    /// Whether the data source dropped entries before they were filtered.
+    pub fn is_truncated(&self) -> bool {
+        self.num_after_truncation < self.total
+    }
+
+    /// Whether filtered entries were left out of the result to respect the limit.
+    pub fn is_limited(&self) -> bool {
+        self.num_filtered
+            .is_some_and(|num_filtered| num_filtered > self.result.len())
+    }
+}
+
+/// Health of a single cluster component.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct ComponentHealth {
+    pub healthy: bool,
+    /// Response body or error describing the health of the component
+    pub message: Option<String>,
+}
+
+/// Health of the cluster as seen through the dashboard.
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct ClusterHealth {
+    pub gcs: ComponentHealth,
+    pub raylet: ComponentHealth,
+    /// Version information, if the dashboard was reachable
+    pub version: Option<RayVersionResponse>,
+}
+
+impl ClusterHealth {
+    /// Whether the dashboard, GCS and raylet are all healthy.
+    pub fn is_healthy(&self) -> bool {
+        self.gcs.healthy && self.raylet.healthy && self.version.is_some()
+    }
+
+    /// Describe the first unhealthy component, if any.
+    pub fn unhealthy_reason(&self) -> Option<String> {
+        let describe = |name: &str, health: &ComponentHealth| {
+            format!(
+                "{} is unhealthy: {}",
+                name,
+                health.message.as_deref().unwrap_or("no message")
+            )
+        };
+
+        if self.version.is_none() {
+            Some("Dashboard is unreachable".to_string())
+        } else if !self.gcs.healthy {
+            Some(describe("GCS", &self.gcs))
+        } else if !self.raylet.healthy {
+            Some(describe("Raylet", &self.raylet))
+        } else {
+            None
+        }
+    }
+}
@@ -0,0 +1,171 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Builder for a job entrypoint command.
+///
+/// Ray runs the entrypoint through a shell, so every argument is POSIX-quoted when rendered.
+/// This makes it safe to pass user-provided values that contain spaces or shell metacharacters.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Entrypoint {
+    program: String,
+    args: Vec<String>,
+    env: Vec<(String, String)>,
+    working_dir: Option<PathBuf>,
+}
+
+impl Entrypoint {
+    /// Create a new Entrypoint running the given program.
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Add a single argument to the command.
+    pub fn with_arg(mut self, arg: impl Into<String>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+
+    /// Add multiple arguments to the command.
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Prefix the command with an environment variable assignment. The value is quoted.
+    ///
+    /// Fails if the key is not a valid shell variable name (`[A-Za-z_][A-Za-z0-9_]*`),
+    /// since keys cannot be quoted.
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> crate::Result<Self> {
+        let key = key.into();
+        if !is_variable_name(&key) {
+            return Err(crate::Error::InvalidEnvVar(key));
+        }
+        self.env.push((key, value.into()));
+        Ok(self)
+    }
+
+    /// Change into the given directory before running the command.
+    pub fn with_working_dir(mut self, working_dir: &Path) -> Self {
+        self.working_dir = Some(working_dir.to_path_buf());
+        self
+    }
+
+    /// Render the entrypoint as a single shell command string.
+    pub fn render(&self) -> String {
+        let mut command = String::new();
+
+        if let Some(ref working_dir) = self.working_dir {
+            command.push_str("cd ");
+            command.push_str(&quote(&working_dir.to_string_lossy()));
+            command.push_str(" && ");
+        }
+
+        for (key, value) in &self.env {
+            command.push_str(key);
+            command.push('=');
+            command.push_str(&quote(value));
+            command.push(' ');
+        }
+
+        command.push_str(&quote(&self.program));
+        for arg in &self.args {
+            command.push(' ');
+            command.push_str(&quote(arg));
+        }
+
+        command
+    }
+}
+
+impl fmt::Display for Entrypoint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.render())
+    }
+}
+
+impl From<Entrypoint> for String {
+    fn from(entrypoint: Entrypoint) -> Self {
+        entrypoint.render()
+    }
+}
+
+fn is_variable_name(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars.next().is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+/// Quote a single word for a POSIX shell.
+/// Words made only of safe characters are returned unchanged, everything else is single-quoted.
+/// Words containing `=` are always quoted, so a program is never read as an assignment.
+pub fn quote(word: &str) -> String {
+    let is_safe = |c: char| c.is_ascii_alphanumeric() || "-_./:,+@%".contains(c);
+
+    if !word.is_empty() && word.chars().all(is_safe) {
+        return word.to_string();
+    }
+
+    // A single quote cannot appear inside single quotes, so close the quoting,
+    // emit an escaped quote, and reopen it
+    format!("'{}'", word.replace('\'', r"'\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_quote_safe_word() {
+        assert_eq!(quote("train.py"), "train.py");
+        assert_eq!(quote("--lr:0.1"), "--lr:0.1");
+    }
+
+    #[test]
+    fn test_quote_unsafe_word() {
+        assert_eq!(quote(""), "''");
+        assert_eq!(quote("hello world"), "'hello world'");
+        assert_eq!(quote("$(rm -rf /)"), "'$(rm -rf /)'");
+        assert_eq!(quote("it's"), r"'it'\''s'");
+        assert_eq!(quote("A=b"), "'A=b'");
+        assert_eq!(quote("--epochs=10"), "'--epochs=10'");
+    }
+
+    #[test]
+    fn test_render_command() {
+        let entrypoint = Entrypoint::new("python")
+            .with_args(["train.py", "--name"])
+            .with_arg("my run; echo pwned");
+        assert_eq!(entrypoint.render(), "python train.py --name 'my run; echo pwned'");
+    }
+
+    #[test]
+    fn test_render_with_env_and_working_dir() {
+        let entrypoint = Entrypoint::new("python")
+            .with_arg("main.py")
+            .with_env("MODE", "fast mode")
+            .unwrap()
+            .with_working_dir(Path::new("/opt/my app"));
+        assert_eq!(
+            entrypoint.render(),
+            "cd '/opt/my app' && MODE='fast mode' python main.py"
+        );
+    }
+
+    #[test]
+    fn test_env_key_must_be_variable_name() {
+        for key in ["X;rm -rf ~;Y", "", "1ABC", "A-B", "A B"] {
+            assert!(matches!(
+                Entrypoint::new("python").with_env(key, "1"),
+                Err(crate::Error::InvalidEnvVar(_))
+            ));
+        }
+        assert!(Entrypoint::new("python").with_env("_CUDA_VISIBLE_1", "0").is_ok());
+    }
+}
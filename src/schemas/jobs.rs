@@ -1,6 +1,8 @@
 use std::collections::HashMap;
+use std::fmt;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use crate::schemas::env::RuntimeEnv;
+use crate::schemas::{entrypoint::Entrypoint, env::RuntimeEnv};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Deserialize, serde::Serialize)]
 pub enum JobType {
@@ -18,6 +20,14 @@ pub enum JobStatus {
 }
 
 impl JobStatus {
+    pub const ALL: [JobStatus; 5] = [
+        JobStatus::PENDING,
+        JobStatus::RUNNING,
+        JobStatus::STOPPED,
+        JobStatus::SUCCEEDED,
+        JobStatus::FAILED,
+    ];
+
     pub fn is_terminal(&self) -> bool {
         matches!(
             self,
@@ -54,6 +64,16 @@ impl JobSubmitRequest {
         }
     }
 
+    /// Create a new JobSubmitRequest running a program with arguments.
+    /// The program and arguments are shell-quoted, see [`Entrypoint`] for more options.
+    pub fn from_command<I, S>(program: impl Into<String>, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        Self::new(Entrypoint::new(program).with_args(args))
+    }
+
     /// Set the submission ID for the job.
     pub fn with_submission_id(mut self, submission_id: impl Into<String>) -> Self {
         self.submission_id = Some(submission_id.into());
@@ -115,6 +135,7 @@ pub struct JobDriverInfo {
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
 pub struct JobDetails {
+    /// Sent as `type` by Ray and serialized as `job_type`, which files written by the SDK use
     #[serde(alias = "type")]
     pub job_type: JobType,
     pub entrypoint: String,
@@ -130,6 +151,95 @@ pub struct JobDetails {
     pub driver_agent_http_address: Option<String>,
     pub driver_node_id: Option<String>,
     pub driver_exit_code: Option<i32>,
+    pub entrypoint_num_cpus: Option<f64>,
+    pub entrypoint_num_gpus: Option<f64>,
+    pub entrypoint_memory: Option<u64>,
+    pub entrypoint_resources: Option<HashMap<String, f64>>,
+}
+
+impl JobDetails {
+    /// Time the job was submitted.
+    pub fn started_at(&self) -> Option<SystemTime> {
+        self.start_time.map(millis_to_system_time)
+    }
+
+    /// Time the job reached a terminal state.
+    pub fn ended_at(&self) -> Option<SystemTime> {
+        self.end_time.map(millis_to_system_time)
+    }
+
+    /// How long the job ran, from submission until it reached a terminal state.
+    /// Returns `None` while the job has not finished.
+    pub fn duration(&self) -> Option<Duration> {
+        match (self.start_time, self.end_time) {
+            (Some(start), Some(end)) => Some(Duration::from_millis(end.saturating_sub(start))),
+            _ => None,
+        }
+    }
+
+    /// How long a `PENDING` job has been waiting since submission.
+    /// Ray does not record when the driver started, so this is `None` once the job left `PENDING`.
+    pub fn queued_for(&self) -> Option<Duration> {
+        if self.status != JobStatus::PENDING {
+            return None;
+        }
+        let started_at = self.started_at()?;
+        Some(SystemTime::now().duration_since(started_at).unwrap_or_default())
+    }
+
+    /// Time the job was submitted as a `chrono` timestamp.
+    #[cfg(feature = "chrono")]
+    pub fn start_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.started_at().map(Into::into)
+    }
+
+    /// Time the job reached a terminal state as a `chrono` timestamp.
+    #[cfg(feature = "chrono")]
+    pub fn end_datetime(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.ended_at().map(Into::into)
+    }
+
+    /// Time the job was submitted as a `time` timestamp.
+    #[cfg(feature = "time")]
+    pub fn start_offset_datetime(&self) -> Option<time::OffsetDateTime> {
+        self.started_at().map(Into::into)
+    }
+
+    /// Time the job reached a terminal state as a `time` timestamp.
+    #[cfg(feature = "time")]
+    pub fn end_offset_datetime(&self) -> Option<time::OffsetDateTime> {
+        self.ended_at().map(Into::into)
+    }
+}
+
+impl fmt::Display for JobDetails {
+    /// One-line summary of the job, e.g. `raysubmit_123 [SUCCEEDED] python train.py (ran 12.3s)`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let id = self
+            .submission_id
+            .as_deref()
+            .or(self.driver_info.as_ref().map(|info| info.id.as_str()))
+            .unwrap_or("<unknown>");
+        write!(f, "{} [{:?}] {}", id, self.status, self.entrypoint)?;
+
+        if let Some(duration) = self.duration() {
+            write!(f, " (ran {:.1}s)", duration.as_secs_f64())?;
+        } else if let Some(queued_for) = self.queued_for() {
+            write!(f, " (queued {:.1}s)", queued_for.as_secs_f64())?;
+        }
+
+        if let Some(code) = self.driver_exit_code
+            && code != 0
+        {
+            write!(f, " exit code {}", code)?;
+        }
+
+        Ok(())
+    }
+}
+
+fn millis_to_system_time(millis: u64) -> SystemTime {
+    UNIX_EPOCH + Duration::from_millis(millis)
 }
 
 #[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
@@ -158,6 +268,20 @@ impl JobLogsResponse {
     }
 }
 
+#[cfg(test)]
+impl JobDetails {
+    /// Submission job with the given ID and status and every optional field unset, for tests.
+    pub(crate) fn test_job(submission_id: &str, status: JobStatus) -> Self {
+        serde_json::from_value(serde_json::json!({
+            "type": "SUBMISSION",
+            "entrypoint": "echo hello",
+            "status": status,
+            "submission_id": submission_id,
+        }))
+        .expect("Test job is valid")
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -182,6 +306,18 @@ mod tests {
         assert_eq!(deserialized, JobStatus::RUNNING);
     }
 
+    #[test]
+    fn test_job_details_type_round_trip() {
+        let details = JobDetails::test_job("raysubmit_123", JobStatus::RUNNING);
+        let serialized = serde_json::to_value(&details).unwrap();
+        assert_eq!(serialized["job_type"], "SUBMISSION");
+        assert!(serialized.get("type").is_none());
+
+        let deserialized: JobDetails = serde_json::from_value(serialized.clone()).unwrap();
+        assert_eq!(deserialized.job_type, JobType::SUBMISSION);
+        assert_eq!(serde_json::to_value(&deserialized).unwrap(), serialized);
+    }
+
     #[test]
     fn test_job_submit_request_builder() {
         let request = JobSubmitRequest::new("python script.py")
@@ -198,4 +334,47 @@ mod tests {
         assert!(request.metadata.is_some());
         assert!(request.metadata.unwrap().get("environment").unwrap() == "dev")
     }
+
+    fn job_details(status: JobStatus, start_time: Option<u64>, end_time: Option<u64>) -> JobDetails {
+        JobDetails {
+            start_time,
+            end_time,
+            ..JobDetails::test_job("raysubmit_123", status)
+        }
+    }
+
+    #[test]
+    fn test_job_details_times() {
+        let details = job_details(JobStatus::SUCCEEDED, Some(1_000), Some(13_300));
+        assert_eq!(details.started_at(), Some(UNIX_EPOCH + Duration::from_secs(1)));
+        assert_eq!(details.duration(), Some(Duration::from_millis(12_300)));
+        assert_eq!(details.queued_for(), None);
+
+        let details = job_details(JobStatus::RUNNING, Some(1_000), None);
+        assert_eq!(details.ended_at(), None);
+        assert_eq!(details.duration(), None);
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn test_job_details_chrono() {
+        let details = job_details(JobStatus::SUCCEEDED, Some(1_000), Some(13_300));
+        let start = details.start_datetime().unwrap();
+        assert_eq!(start.timestamp_millis(), 1_000);
+    }
+
+    #[test]
+    fn test_job_details_display() {
+        let details = job_details(JobStatus::SUCCEEDED, Some(1_000), Some(13_300));
+        assert_eq!(
+            details.to_string(),
+            "raysubmit_123 [SUCCEEDED] echo hello (ran 12.3s)"
+        );
+    }
+
+    #[test]
+    fn test_job_submit_request_from_command() {
+        let request = JobSubmitRequest::from_command("python", ["train.py", "--name", "my run"]);
+        assert_eq!(request.entrypoint, "python train.py --name 'my run'");
+    }
 }
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+#[derive(Debug, Clone, serde::Deserialize, serde::Serialize)]
+pub struct LogListResult {
+    pub result: HashMap<String, Vec<String>>,
+}
+
+/// Log files available on a node, grouped by category (e.g. `worker_out`, `raylet`).
+#[derive(Debug, Clone, Default)]
+pub struct LogListing {
+    pub categories: HashMap<String, Vec<String>>,
+}
+
+impl LogListing {
+    /// All file names across categories.
+    pub fn files(&self) -> impl Iterator<Item = &str> {
+        self.categories.values().flatten().map(String::as_str)
+    }
+}
+
+impl From<LogListResult> for LogListing {
+    fn from(result: LogListResult) -> Self {
+        Self {
+            categories: result.result,
+        }
+    }
+}
+
+/// Identifies a log file for `/api/v0/logs/file` and `/api/v0/logs/stream`.
+///
+/// A log is located either by `node_id`/`node_ip` plus `filename` or `pid`,
+/// or by `actor_id`, `task_id` or `submission_id` alone.
+/// The endpoints support tailing by line count but not byte ranges.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct LogQuery {
+    pub node_id: Option<String>,
+    pub node_ip: Option<String>,
+    pub filename: Option<String>,
+    pub actor_id: Option<String>,
+    pub task_id: Option<String>,
+    pub pid: Option<u32>,
+    pub submission_id: Option<String>,
+    /// Which stream of an actor, task or pid to read: `out` or `err`
+    pub suffix: Option<String>,
+    /// Number of lines to return from the end of the file, `-1` for the whole file
+    pub lines: Option<i64>,
+}
+
+impl LogQuery {
+    /// Create a query for a log file on a node.
+    pub fn for_file(node_id: impl Into<String>, filename: impl Into<String>) -> Self {
+        Self {
+            node_id: Some(node_id.into()),
+            filename: Some(filename.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a query for the logs of an actor.
+    pub fn for_actor(actor_id: impl Into<String>) -> Self {
+        Self {
+            actor_id: Some(actor_id.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a query for the logs of a task.
+    pub fn for_task(task_id: impl Into<String>) -> Self {
+        Self {
+            task_id: Some(task_id.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Create a query for the logs of a process on a node.
+    pub fn for_pid(node_id: impl Into<String>, pid: u32) -> Self {
+        Self {
+            node_id: Some(node_id.into()),
+            pid: Some(pid),
+            ..Default::default()
+        }
+    }
+
+    /// Create a query for the driver logs of a submitted job.
+    pub fn for_submission(submission_id: impl Into<String>) -> Self {
+        Self {
+            submission_id: Some(submission_id.into()),
+            ..Default::default()
+        }
+    }
+
+    /// Set the node IP used to locate the log, as an alternative to the node ID.
+    pub fn with_node_ip(mut self, node_ip: impl Into<String>) -> Self {
+        self.node_ip = Some(node_ip.into());
+        self
+    }
+
+    /// Set which stream of an actor, task or pid to read: `out` or `err`.
+    pub fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = Some(suffix.into());
+        self
+    }
+
+    /// Only return the last `lines` lines.
+    pub fn with_lines(mut self, lines: i64) -> Self {
+        self.lines = Some(lines);
+        self
+    }
+
+    /// Query parameters for the log endpoints.
+    pub fn to_params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        let mut push = |key: &'static str, value: Option<String>| {
+            if let Some(value) = value {
+                params.push((key, value));
+            }
+        };
+
+        push("node_id", self.node_id.clone());
+        push("node_ip", self.node_ip.clone());
+        push("filename", self.filename.clone());
+        push("actor_id", self.actor_id.clone());
+        push("task_id", self.task_id.clone());
+        push("pid", self.pid.map(|pid| pid.to_string()));
+        push("submission_id", self.submission_id.clone());
+        push("suffix", self.suffix.clone());
+        push("lines", self.lines.map(|lines| lines.to_string()));
+
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::schemas::common::DashboardResponse;
+
+    #[test]
+    fn test_log_query_params() {
+        let query = LogQuery::for_file("node123", "raylet.out").with_lines(100);
+        assert_eq!(
+            query.to_params(),
+            vec![
+                ("node_id", "node123".to_string()),
+                ("filename", "raylet.out".to_string()),
+                ("lines", "100".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_log_listing_deserialize() {
+        let json = serde_json::json!({
+            "result": true,
+            "msg": "",
+            "data": {"result": {"raylet": ["raylet.out", "raylet.err"], "gcs_server": ["gcs_server.out"]}},
+        });
+        let response: DashboardResponse<LogListResult> = serde_json::from_value(json).unwrap();
+        let listing = LogListing::from(response.data);
+        assert_eq!(listing.files().count(), 3);
+    }
+}
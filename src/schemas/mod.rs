@@ -1,3 +1,8 @@
+pub mod capabilities;
+pub mod cluster;
 pub mod common;
+pub mod entrypoint;
 pub mod env;
 pub mod jobs;
+pub mod logs;
+pub mod query;
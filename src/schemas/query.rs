@@ -0,0 +1,306 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use crate::schemas::jobs::{JobDetails, JobStatus, JobType};
+
+/// Field used to sort the results of a [`JobQuery`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum JobSortKey {
+    StartTime,
+    EndTime,
+}
+
+/// Direction used to sort the results of a [`JobQuery`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// Filters, sorting and pagination for listing jobs.
+///
+/// Times are milliseconds since the Unix epoch, matching [`JobDetails::start_time`].
+/// An empty query matches every job.
+#[derive(Debug, Default, Clone)]
+pub struct JobQuery {
+    pub statuses: Option<HashSet<JobStatus>>,
+    pub job_type: Option<JobType>,
+    pub metadata: HashMap<String, String>,
+    pub submission_id_prefix: Option<String>,
+    /// Only match jobs started at or after this time
+    pub started_after: Option<u64>,
+    /// Only match jobs started strictly before this time
+    pub started_before: Option<u64>,
+    pub sort: Option<(JobSortKey, SortOrder)>,
+    pub limit: Option<usize>,
+    pub offset: usize,
+}
+
+impl JobQuery {
+    /// Create a new JobQuery matching every job.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Add a status to the set of statuses to match.
+    pub fn with_status(mut self, status: JobStatus) -> Self {
+        self.statuses.get_or_insert_with(HashSet::new).insert(status);
+        self
+    }
+
+    /// Add multiple statuses to the set of statuses to match.
+    pub fn with_statuses(mut self, statuses: impl IntoIterator<Item = JobStatus>) -> Self {
+        self.statuses.get_or_insert_with(HashSet::new).extend(statuses);
+        self
+    }
+
+    /// Only match jobs of the given type.
+    pub fn with_job_type(mut self, job_type: JobType) -> Self {
+        self.job_type = Some(job_type);
+        self
+    }
+
+    /// Only match jobs whose metadata contains the given key and value.
+    pub fn with_metadata_item(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// Only match jobs whose submission ID starts with the given prefix.
+    pub fn with_submission_id_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.submission_id_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Only match jobs started at or after the given time.
+    pub fn with_started_after(mut self, time_ms: u64) -> Self {
+        self.started_after = Some(time_ms);
+        self
+    }
+
+    /// Only match jobs started before the given time.
+    pub fn with_started_before(mut self, time_ms: u64) -> Self {
+        self.started_before = Some(time_ms);
+        self
+    }
+
+    /// Sort the results. Jobs without the sort field are always placed last.
+    pub fn with_sort(mut self, key: JobSortKey, order: SortOrder) -> Self {
+        self.sort = Some((key, order));
+        self
+    }
+
+    /// Return at most this many jobs.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Skip this many jobs before returning results.
+    pub fn with_offset(mut self, offset: usize) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    /// Check if a job matches the filters of the query.
+    pub fn matches(&self, job: &JobDetails) -> bool {
+        if let Some(ref statuses) = self.statuses
+            && !statuses.contains(&job.status)
+        {
+            return false;
+        }
+
+        if let Some(job_type) = self.job_type
+            && job.job_type != job_type
+        {
+            return false;
+        }
+
+        if !self.metadata.is_empty() {
+            let Some(ref metadata) = job.metadata else {
+                return false;
+            };
+            if !self.metadata.iter().all(|(k, v)| metadata.get(k) == Some(v)) {
+                return false;
+            }
+        }
+
+        if let Some(ref prefix) = self.submission_id_prefix
+            && !job
+                .submission_id
+                .as_deref()
+                .is_some_and(|id| id.starts_with(prefix.as_str()))
+        {
+            return false;
+        }
+
+        if self.started_after.is_some() || self.started_before.is_some() {
+            let Some(start_time) = job.start_time else {
+                return false;
+            };
+            if self.started_after.is_some_and(|after| start_time < after) {
+                return false;
+            }
+            if self.started_before.is_some_and(|before| start_time >= before) {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    /// Filters of the query the state API jobs endpoint (`/api/v0/jobs`) can apply, as
+    /// `(key, predicate, value)`. Filters are combined with AND, so statuses are sent as the
+    /// statuses to exclude. Metadata, submission ID prefix and start time are only applied client-side.
+    pub(crate) fn state_api_filters(&self) -> Vec<(&'static str, &'static str, String)> {
+        let mut filters = Vec::new();
+        if let Some(ref statuses) = self.statuses {
+            for status in JobStatus::ALL.iter().filter(|status| !statuses.contains(status)) {
+                filters.push(("status", "!=", format!("{:?}", status)));
+            }
+        }
+        if let Some(job_type) = self.job_type {
+            filters.push(("type", "=", format!("{:?}", job_type)));
+        }
+        filters
+    }
+
+    /// Number of jobs the state API may truncate its results to, or `None` if truncating before
+    /// the client-side filters and sorting are applied could drop matching jobs.
+    pub(crate) fn state_api_limit(&self) -> Option<usize> {
+        let client_side = !self.metadata.is_empty()
+            || self.submission_id_prefix.is_some()
+            || self.started_after.is_some()
+            || self.started_before.is_some()
+            || self.sort.is_some();
+        match self.limit {
+            Some(limit) if !client_side => Some(self.offset.saturating_add(limit)),
+            _ => None,
+        }
+    }
+
+    /// Filter, sort and paginate a list of jobs.
+    pub fn apply(&self, jobs: Vec<JobDetails>) -> Vec<JobDetails> {
+        self.apply_by(jobs, |job| job)
+    }
+
+    /// Filter, sort and paginate items by the job details they hold.
+    pub(crate) fn apply_by<T>(&self, items: Vec<T>, details: impl Fn(&T) -> &JobDetails) -> Vec<T> {
+        let mut jobs: Vec<T> = items
+            .into_iter()
+            .filter(|item| self.matches(details(item)))
+            .collect();
+
+        if let Some((key, order)) = self.sort {
+            let field = |item: &T| match key {
+                JobSortKey::StartTime => details(item).start_time,
+                JobSortKey::EndTime => details(item).end_time,
+            };
+            jobs.sort_by(|a, b| match (field(a), field(b)) {
+                (Some(a), Some(b)) => match order {
+                    SortOrder::Ascending => a.cmp(&b),
+                    SortOrder::Descending => b.cmp(&a),
+                },
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            });
+        }
+
+        let jobs = jobs.into_iter().skip(self.offset);
+        match self.limit {
+            Some(limit) => jobs.take(limit).collect(),
+            None => jobs.collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn job(submission_id: &str, status: JobStatus, start_time: Option<u64>) -> JobDetails {
+        JobDetails {
+            start_time,
+            metadata: Some(HashMap::from([("team".to_string(), "ml".to_string())])),
+            ..JobDetails::test_job(submission_id, status)
+        }
+    }
+
+    fn ids(jobs: &[JobDetails]) -> Vec<&str> {
+        jobs.iter()
+            .filter_map(|job| job.submission_id.as_deref())
+            .collect()
+    }
+
+    #[test]
+    fn test_empty_query_matches_all() {
+        let jobs = vec![
+            job("a", JobStatus::RUNNING, None),
+            job("b", JobStatus::FAILED, None),
+        ];
+        assert_eq!(JobQuery::new().apply(jobs).len(), 2);
+    }
+
+    #[test]
+    fn test_filters() {
+        let jobs = vec![
+            job("sweep-1", JobStatus::RUNNING, Some(100)),
+            job("sweep-2", JobStatus::FAILED, Some(200)),
+            job("other-3", JobStatus::RUNNING, Some(300)),
+        ];
+
+        let query = JobQuery::new()
+            .with_statuses([JobStatus::RUNNING, JobStatus::PENDING])
+            .with_submission_id_prefix("sweep-");
+        assert_eq!(ids(&query.apply(jobs.clone())), vec!["sweep-1"]);
+
+        let query = JobQuery::new().with_started_after(200).with_started_before(300);
+        assert_eq!(ids(&query.apply(jobs.clone())), vec!["sweep-2"]);
+
+        let query = JobQuery::new().with_metadata_item("team", "infra");
+        assert!(query.apply(jobs.clone()).is_empty());
+
+        let query = JobQuery::new().with_job_type(JobType::DRIVER);
+        assert!(query.apply(jobs).is_empty());
+    }
+
+    #[test]
+    fn test_state_api_params() {
+        let query = JobQuery::new()
+            .with_statuses([JobStatus::PENDING, JobStatus::RUNNING])
+            .with_job_type(JobType::SUBMISSION)
+            .with_limit(10)
+            .with_offset(5);
+        assert_eq!(
+            query.state_api_filters(),
+            [
+                ("status", "!=", "STOPPED".to_string()),
+                ("status", "!=", "SUCCEEDED".to_string()),
+                ("status", "!=", "FAILED".to_string()),
+                ("type", "=", "SUBMISSION".to_string()),
+            ]
+        );
+        assert_eq!(query.state_api_limit(), Some(15));
+
+        let query = query.with_metadata_item("team", "ml");
+        assert_eq!(query.state_api_limit(), None);
+    }
+
+    #[test]
+    fn test_sort_and_paginate() {
+        let jobs = vec![
+            job("a", JobStatus::RUNNING, Some(200)),
+            job("b", JobStatus::RUNNING, None),
+            job("c", JobStatus::RUNNING, Some(300)),
+            job("d", JobStatus::RUNNING, Some(100)),
+        ];
+
+        let query = JobQuery::new().with_sort(JobSortKey::StartTime, SortOrder::Descending);
+        assert_eq!(ids(&query.apply(jobs.clone())), vec!["c", "a", "d", "b"]);
+
+        let query = query.with_offset(1).with_limit(2);
+        assert_eq!(ids(&query.apply(jobs)), vec!["a", "d"]);
+    }
+}
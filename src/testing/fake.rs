@@ -0,0 +1,291 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use async_trait::async_trait;
+use futures::{StreamExt, stream::BoxStream};
+
+use crate::{
+    client::{cluster::ClusterAPI, jobs::JobSubmissionAPI, logs::LogsAPI, packages::PackagesAPI},
+    schemas::{
+        cluster::ClusterStatus,
+        jobs::{
+            JobDeleteResponse, JobDetails, JobLogsResponse, JobStatus, JobStopResponse, JobSubmitRequest,
+            JobSubmitResponse,
+        },
+        logs::{LogListing, LogQuery},
+    },
+    testing::script::{JobScript, JobTable},
+};
+
+#[derive(Debug, Default)]
+struct FakeState {
+    jobs: JobTable,
+    packages: HashMap<String, usize>,
+    cluster_status: ClusterStatus,
+    logs: LogListing,
+}
+
+/// In-memory implementation of the dashboard APIs for tests that do not need HTTP.
+///
+/// Jobs follow a [`JobScript`] like in `MockRayDashboard`. `get_log` only serves the driver logs
+/// of submitted jobs, while `list_logs` lists the files added with `add_log_file`.
+/// Clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct FakeRayDashboard {
+    state: Arc<Mutex<FakeState>>,
+}
+
+impl FakeRayDashboard {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the script used for jobs submitted without a specific script.
+    pub fn set_default_script(&self, script: JobScript) {
+        self.state().jobs.set_default_script(script);
+    }
+
+    /// Set the script used for the job submitted with the given submission ID.
+    pub fn script_job(&self, submission_id: impl Into<String>, script: JobScript) {
+        self.state().jobs.script_job(submission_id.into(), script);
+    }
+
+    /// Force a job into a status.
+    pub fn set_job_status(&self, submission_id: &str, status: JobStatus) {
+        let _ = self.state().jobs.set_status(submission_id, status);
+    }
+
+    /// Append output to the logs of a job.
+    pub fn append_logs(&self, submission_id: &str, logs: &str) {
+        let _ = self.state().jobs.append_logs(submission_id, logs);
+    }
+
+    /// Set the cluster status returned by `get_cluster_status`.
+    pub fn set_cluster_status(&self, status: ClusterStatus) {
+        self.state().cluster_status = status;
+    }
+
+    /// Add a log file listed under a category by `list_logs`.
+    pub fn add_log_file(&self, category: impl Into<String>, filename: impl Into<String>) {
+        self.state()
+            .logs
+            .categories
+            .entry(category.into())
+            .or_default()
+            .push(filename.into());
+    }
+
+    /// Details of all jobs, without advancing their scripts.
+    pub fn jobs(&self) -> Vec<JobDetails> {
+        self.state().jobs.list()
+    }
+
+    /// URIs and sizes of all uploaded packages.
+    pub fn packages(&self) -> HashMap<String, usize> {
+        self.state().packages.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, FakeState> {
+        self.state.lock().expect("Fake dashboard state is not poisoned")
+    }
+}
+
+#[async_trait]
+impl JobSubmissionAPI for FakeRayDashboard {
+    async fn submit_job(&self, payload: &JobSubmitRequest) -> crate::Result<JobSubmitResponse> {
+        let payload = self.resolve_working_dir(payload).await?;
+        let submission_id = self.state().jobs.submit(payload)?;
+        Ok(JobSubmitResponse { submission_id })
+    }
+
+    async fn list_jobs(&self) -> crate::Result<Vec<JobDetails>> {
+        Ok(self.state().jobs.list())
+    }
+
+    async fn get_job_details(&self, submission_id: &str) -> crate::Result<JobDetails> {
+        Ok(self.state().jobs.poll(submission_id)?)
+    }
+
+    async fn delete_job(&self, submission_id: &str) -> crate::Result<JobDeleteResponse> {
+        self.state().jobs.delete(submission_id)?;
+        Ok(JobDeleteResponse { deleted: true })
+    }
+
+    async fn stop_job(&self, submission_id: &str) -> crate::Result<JobStopResponse> {
+        let stopped = self.state().jobs.stop(submission_id)?;
+        Ok(JobStopResponse { stopped })
+    }
+
+    async fn get_job_logs(&self, submission_id: &str) -> crate::Result<JobLogsResponse> {
+        let logs = self.state().jobs.logs(submission_id)?;
+        Ok(JobLogsResponse { logs })
+    }
+}
+
+#[async_trait]
+impl PackagesAPI for FakeRayDashboard {
+    async fn package_exists(&self, package_uri: &str) -> crate::Result<bool> {
+        Ok(self.state().packages.contains_key(package_uri))
+    }
+
+    async fn upload_package(&self, package_uri: &str, data: Vec<u8>) -> crate::Result<()> {
+        self.state().packages.insert(package_uri.to_string(), data.len());
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl LogsAPI for FakeRayDashboard {
+    async fn list_logs(&self, _node_id: &str, glob: Option<&str>) -> crate::Result<LogListing> {
+        let logs = self.state().logs.clone();
+        let Some(pattern) = glob.map(|glob| glob.trim_matches('*')) else {
+            return Ok(logs);
+        };
+
+        let categories = logs
+            .categories
+            .into_iter()
+            .map(|(category, files)| {
+                let files = files.into_iter().filter(|file| file.contains(pattern)).collect();
+                (category, files)
+            })
+            .collect();
+        Ok(LogListing { categories })
+    }
+
+    async fn get_log(&self, query: &LogQuery) -> crate::Result<String> {
+        let logs = match query.submission_id {
+            Some(ref submission_id) => self.state().jobs.logs(submission_id)?,
+            None => {
+                return Err(crate::Error::Generic(format!(
+                    "Fake dashboard only serves driver logs of submitted jobs: {:?}",
+                    query
+                )));
+            }
+        };
+
+        let Some(lines) = query.lines.filter(|lines| *lines >= 0) else {
+            return Ok(logs);
+        };
+        let all_lines: Vec<&str> = logs.lines().collect();
+        let start = all_lines.len().saturating_sub(lines as usize);
+        Ok(all_lines[start..]
+            .iter()
+            .map(|line| format!("{}\n", line))
+            .collect())
+    }
+
+    async fn follow_log(&self, query: &LogQuery) -> crate::Result<BoxStream<'static, crate::Result<String>>> {
+        let logs = self.get_log(query).await?;
+        Ok(futures::stream::once(async move { Ok(logs) }).boxed())
+    }
+}
+
+#[async_trait]
+impl ClusterAPI for FakeRayDashboard {
+    async fn get_cluster_status(&self) -> crate::Result<ClusterStatus> {
+        Ok(self.state().cluster_status.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{RayDashboard, RunOptions, WaitOptions};
+
+    #[tokio::test]
+    async fn test_fake_run_job_as_trait_object() {
+        let fake = FakeRayDashboard::new();
+        fake.set_default_script(JobScript::default().with_logs("ABC123\n"));
+        let dashboard: Arc<dyn RayDashboard> = Arc::new(fake.clone());
+
+        let wait = WaitOptions::new().with_initial_interval(std::time::Duration::from_millis(1));
+        let outcome = dashboard
+            .run_job(
+                &JobSubmitRequest::new("echo 'ABC123'"),
+                RunOptions::new().with_wait_options(wait),
+            )
+            .await
+            .unwrap();
+        assert!(outcome.is_success());
+        assert_eq!(outcome.logs.as_deref(), Some("ABC123\n"));
+
+        let job = fake.jobs().remove(0);
+        let driver_logs = dashboard.get_driver_logs(&job, Some(1)).await.unwrap();
+        assert_eq!(driver_logs, "ABC123\n");
+    }
+
+    #[tokio::test]
+    async fn test_fake_watch_as_trait_object() {
+        use futures::StreamExt;
+
+        let fake = FakeRayDashboard::new();
+        let dashboard: Arc<dyn RayDashboard> = Arc::new(fake.clone());
+        let payload = JobSubmitRequest::new("echo 'ABC123'").with_submission_id("watched");
+        dashboard.submit_job(&payload).await.unwrap();
+
+        let filter: crate::client::watch::JobFilter = Arc::new(|job| job.submission_id.is_some());
+        let created = dashboard
+            .watch_jobs(filter, std::time::Duration::from_millis(1))
+            .next()
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(matches!(created, crate::client::watch::JobsEvent::Created(_)));
+
+        let statuses: Vec<JobStatus> = dashboard
+            .watch_job("watched", std::time::Duration::from_millis(1))
+            .map(|event| event.unwrap().status)
+            .collect()
+            .await;
+        assert_eq!(statuses.last(), Some(&JobStatus::SUCCEEDED));
+    }
+
+    #[tokio::test]
+    async fn test_fake_missing_job_is_not_found() {
+        let fake = FakeRayDashboard::new();
+        let error = fake.get_job_details("missing").await.unwrap_err();
+        assert!(
+            matches!(error, crate::Error::Request(ref e) if e.status() == Some(http::StatusCode::NOT_FOUND))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fake_delete_requires_terminal_job() {
+        let fake = FakeRayDashboard::new();
+        fake.set_default_script(JobScript::running());
+        let submission_id = fake
+            .submit_job(&JobSubmitRequest::new("sleep 60"))
+            .await
+            .unwrap()
+            .submission_id;
+
+        let error = fake.delete_job(&submission_id).await.unwrap_err();
+        assert!(
+            matches!(error, crate::Error::Request(ref e) if e.status() == Some(http::StatusCode::BAD_REQUEST))
+        );
+        assert!(fake.stop_job(&submission_id).await.unwrap().stopped);
+        assert!(fake.delete_job(&submission_id).await.unwrap().deleted);
+        assert!(fake.jobs().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_fake_uploads_working_dir() {
+        let fake = FakeRayDashboard::new();
+        let temp_dir = tempfile::tempdir().unwrap();
+        std::fs::write(temp_dir.path().join("script.py"), "print('hi')").unwrap();
+
+        let runtime_env = crate::schemas::env::RuntimeEnv::new().with_working_dir(temp_dir.path());
+        let payload = JobSubmitRequest::new("python script.py").with_runtime_env(runtime_env);
+        fake.submit_job(&payload).await.unwrap();
+
+        let package_uri = fake.packages().into_keys().next().expect("Package was uploaded");
+        let working_dir = fake.jobs()[0]
+            .runtime_env
+            .as_ref()
+            .and_then(|env| env.working_dir.clone());
+        assert_eq!(working_dir, Some(package_uri.into()));
+    }
+}
@@ -0,0 +1,294 @@
+use std::{
+    collections::{HashMap, VecDeque},
+    path::{Path, PathBuf},
+    sync::{Mutex, MutexGuard},
+};
+
+use tracing::{debug, warn};
+
+/// Environment variable selecting the fixture mode in `HttpFixture::from_env`.
+pub const FIXTURE_MODE_ENV: &str = "RAY_FIXTURE_MODE";
+
+/// Endpoints whose responses are streamed and cannot be recorded or replayed as a single body
+const STREAMING_PATHS: &[&str] = &["/api/v0/logs/stream"];
+
+/// A recorded request and the response returned by the dashboard.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize, serde::Serialize)]
+pub struct RecordedExchange {
+    pub method: String,
+    /// Path and query of the request
+    pub path: String,
+    /// Request body if it is valid UTF-8, for reference only
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub request_body: Option<String>,
+    pub status: u16,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content_type: Option<String>,
+    pub body: String,
+}
+
+impl RecordedExchange {
+    fn key(&self) -> (String, String) {
+        (self.method.clone(), self.path.clone())
+    }
+}
+
+/// Contents of a fixture file.
+#[derive(Debug, Clone, Default, serde::Deserialize, serde::Serialize)]
+pub struct FixtureFile {
+    pub exchanges: Vec<RecordedExchange>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixtureMode {
+    /// Send requests to the dashboard and write the exchanges to the fixture file once done
+    Record,
+    /// Serve responses from the fixture file without sending requests
+    Replay,
+}
+
+#[derive(Debug)]
+enum FixtureState {
+    Record(FixtureFile),
+    /// Remaining responses per method and path, in recorded order
+    Replay(HashMap<(String, String), VecDeque<RecordedExchange>>),
+}
+
+/// Records dashboard traffic to a JSON file, or replays it, for deterministic tests.
+///
+/// Requests are matched on method, path and query. Responses to the same request are
+/// replayed in recorded order, with the last one repeated once the others are used up,
+/// so polling loops replay the status transitions they observed while recording.
+/// Request bodies are not matched, and responses are replayed as a single chunk, so
+/// requests to the streamed log endpoint fail in both modes.
+///
+/// When recording, the fixture file is written by `save`, or when the fixture is dropped.
+#[derive(Debug)]
+pub struct HttpFixture {
+    path: PathBuf,
+    mode: FixtureMode,
+    state: Mutex<FixtureState>,
+}
+
+impl HttpFixture {
+    /// Record exchanges to a fixture file, replacing any existing content.
+    pub fn record(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            mode: FixtureMode::Record,
+            state: Mutex::new(FixtureState::Record(FixtureFile::default())),
+        }
+    }
+
+    /// Replay exchanges from a fixture file.
+    pub fn replay(path: impl Into<PathBuf>) -> crate::Result<Self> {
+        let path = path.into();
+        let contents = std::fs::read_to_string(&path)?;
+        let file: FixtureFile = serde_json::from_str(&contents).map_err(std::io::Error::from)?;
+
+        let mut exchanges: HashMap<_, VecDeque<_>> = HashMap::new();
+        for exchange in file.exchanges {
+            exchanges.entry(exchange.key()).or_default().push_back(exchange);
+        }
+
+        Ok(Self {
+            path,
+            mode: FixtureMode::Replay,
+            state: Mutex::new(FixtureState::Replay(exchanges)),
+        })
+    }
+
+    /// Record if `RAY_FIXTURE_MODE` is set to `record`, otherwise replay.
+    pub fn from_env(path: impl Into<PathBuf>) -> crate::Result<Self> {
+        match std::env::var(FIXTURE_MODE_ENV).as_deref() {
+            Ok("record") => Ok(Self::record(path)),
+            _ => Self::replay(path),
+        }
+    }
+
+    pub fn mode(&self) -> FixtureMode {
+        self.mode
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Write the exchanges recorded so far to the fixture file. Does nothing when replaying.
+    pub fn save(&self) -> crate::Result<()> {
+        let contents = match *self.state() {
+            FixtureState::Record(ref file) => {
+                serde_json::to_string_pretty(file).map_err(std::io::Error::from)?
+            }
+            FixtureState::Replay(_) => return Ok(()),
+        };
+        std::fs::write(&self.path, contents)?;
+        Ok(())
+    }
+
+    pub(crate) async fn execute(
+        &self,
+        client: &reqwest::Client,
+        request: reqwest::Request,
+    ) -> crate::Result<reqwest::Response> {
+        let method = request.method().to_string();
+        let path = match request.url().query() {
+            Some(query) => format!("{}?{}", request.url().path(), query),
+            None => request.url().path().to_string(),
+        };
+
+        if STREAMING_PATHS.contains(&request.url().path()) {
+            return Err(crate::Error::Generic(format!(
+                "Streamed responses from {} cannot be recorded or replayed",
+                request.url().path()
+            )));
+        }
+
+        match self.mode {
+            FixtureMode::Record => {
+                let request_body = request
+                    .body()
+                    .and_then(|body| body.as_bytes())
+                    .and_then(|bytes| String::from_utf8(bytes.to_vec()).ok());
+
+                let response = client.execute(request).await?;
+                let status = response.status().as_u16();
+                let content_type = response
+                    .headers()
+                    .get(reqwest::header::CONTENT_TYPE)
+                    .and_then(|value| value.to_str().ok())
+                    .map(String::from);
+                let body = response.text().await?;
+
+                let exchange = RecordedExchange {
+                    method,
+                    path,
+                    request_body,
+                    status,
+                    content_type,
+                    body,
+                };
+                debug!(
+                    "Recording {} {} to {}",
+                    exchange.method,
+                    exchange.path,
+                    self.path.display()
+                );
+                if let FixtureState::Record(ref mut file) = *self.state() {
+                    file.exchanges.push(exchange.clone());
+                }
+                to_response(&exchange)
+            }
+            FixtureMode::Replay => {
+                let exchange = match *self.state() {
+                    FixtureState::Replay(ref mut exchanges) => exchanges
+                        .get_mut(&(method.clone(), path.clone()))
+                        .filter(|queue| !queue.is_empty())
+                        .map(|queue| match queue.len() {
+                            1 => queue[0].clone(),
+                            _ => queue.pop_front().expect("Queue is not empty"),
+                        }),
+                    FixtureState::Record(_) => None,
+                };
+                let exchange = exchange.ok_or_else(|| {
+                    crate::Error::Generic(format!(
+                        "No recorded response for {} {} in {}",
+                        method,
+                        path,
+                        self.path.display()
+                    ))
+                })?;
+                to_response(&exchange)
+            }
+        }
+    }
+
+    fn state(&self) -> MutexGuard<'_, FixtureState> {
+        self.state.lock().expect("Fixture state is not poisoned")
+    }
+}
+
+impl Drop for HttpFixture {
+    fn drop(&mut self) {
+        if let Err(e) = self.save() {
+            warn!("Failed to write fixture {}: {}", self.path.display(), e);
+        }
+    }
+}
+
+fn to_response(exchange: &RecordedExchange) -> crate::Result<reqwest::Response> {
+    let mut builder = http::Response::builder().status(exchange.status);
+    if let Some(ref content_type) = exchange.content_type {
+        builder = builder.header(reqwest::header::CONTENT_TYPE, content_type);
+    }
+    let response = builder
+        .body(exchange.body.clone())
+        .map_err(|e| crate::Error::Generic(format!("Invalid recorded response: {}", e)))?;
+    Ok(response.into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exchange(path: &str, body: &str) -> RecordedExchange {
+        RecordedExchange {
+            method: "GET".to_string(),
+            path: path.to_string(),
+            request_body: None,
+            status: 200,
+            content_type: Some("application/json".to_string()),
+            body: body.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_replay_repeats_last_response() {
+        let file = FixtureFile {
+            exchanges: vec![
+                exchange("/api/jobs/abc", r#"{"status": "RUNNING"}"#),
+                exchange("/api/jobs/abc", r#"{"status": "SUCCEEDED"}"#),
+            ],
+        };
+        let temp = tempfile::NamedTempFile::new().unwrap();
+        std::fs::write(temp.path(), serde_json::to_string(&file).unwrap()).unwrap();
+
+        let fixture = HttpFixture::replay(temp.path()).unwrap();
+        let client = reqwest::Client::new();
+        let mut bodies = Vec::new();
+        for _ in 0..3 {
+            let request = client.get("http://127.0.0.1:8265/api/jobs/abc").build().unwrap();
+            let response = fixture.execute(&client, request).await.unwrap();
+            bodies.push(response.text().await.unwrap());
+        }
+        assert!(bodies[0].contains("RUNNING"));
+        assert!(bodies[1].contains("SUCCEEDED"));
+        assert_eq!(bodies[1], bodies[2]);
+
+        let request = client.get("http://127.0.0.1:8265/api/version").build().unwrap();
+        assert!(fixture.execute(&client, request).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_record_writes_file_once_dropped() {
+        let mock = crate::testing::MockRayDashboard::start().await.unwrap();
+        let temp = tempfile::NamedTempFile::new().unwrap();
+
+        let fixture = std::sync::Arc::new(HttpFixture::record(temp.path()));
+        let client = mock.client().with_fixture(fixture.clone());
+        client.get_version().await.unwrap();
+
+        let http = reqwest::Client::new();
+        let request = http
+            .get(format!("{}/api/v0/logs/stream?lines=10", mock.url()))
+            .build()
+            .unwrap();
+        assert!(fixture.execute(&http, request).await.is_err());
+
+        drop(client);
+        drop(fixture);
+        let file: FixtureFile = serde_json::from_str(&std::fs::read_to_string(temp.path()).unwrap()).unwrap();
+        assert_eq!(file.exchanges.len(), 1);
+        assert_eq!(file.exchanges[0].path, "/api/version");
+    }
+}
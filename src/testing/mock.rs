@@ -0,0 +1,438 @@
+use std::{
+    collections::HashMap,
+    net::SocketAddr,
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+use axum::{
+    Json, Router,
+    body::Bytes,
+    extract::{Path, Query, Request, State},
+    http::{Method, StatusCode},
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+};
+use tokio::task::JoinHandle;
+
+use crate::{
+    RayDashboardClient,
+    schemas::{
+        cluster::ClusterStatusData,
+        common::{DashboardResponse, RayVersionResponse, StateListData, StateListResult},
+        jobs::{
+            JobDeleteResponse, JobDetails, JobLogsResponse, JobStatus, JobStopResponse, JobSubmitRequest,
+            JobSubmitResponse,
+        },
+    },
+    testing::script::{JobScript, JobTable, JobTableError},
+};
+
+/// Ray version reported by the mock dashboard unless overridden.
+pub const MOCK_RAY_VERSION: &str = "2.50.1";
+
+/// A failure returned by the mock dashboard instead of the regular response.
+#[derive(Debug, Clone)]
+pub struct InjectedFailure {
+    /// Only fail requests with this method, or any method if `None`
+    pub method: Option<Method>,
+    /// Only fail requests whose path starts with this prefix
+    pub path_prefix: String,
+    pub status: StatusCode,
+    pub body: String,
+    /// Number of requests to fail, or every matching request if `None`
+    pub times: Option<usize>,
+}
+
+impl InjectedFailure {
+    /// Fail every request whose path starts with the prefix with a 500 error.
+    pub fn new(path_prefix: impl Into<String>) -> Self {
+        Self {
+            method: None,
+            path_prefix: path_prefix.into(),
+            status: StatusCode::INTERNAL_SERVER_ERROR,
+            body: "Injected failure".to_string(),
+            times: None,
+        }
+    }
+
+    /// Only fail requests with the given method.
+    pub fn with_method(mut self, method: Method) -> Self {
+        self.method = Some(method);
+        self
+    }
+
+    /// Set the status code of the failure.
+    pub fn with_status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Set the response body of the failure.
+    pub fn with_body(mut self, body: impl Into<String>) -> Self {
+        self.body = body.into();
+        self
+    }
+
+    /// Only fail the next `times` matching requests. With `0`, no request fails.
+    pub fn with_times(mut self, times: usize) -> Self {
+        self.times = Some(times);
+        self
+    }
+
+    fn matches(&self, method: &Method, path: &str) -> bool {
+        self.times != Some(0)
+            && self.method.as_ref().is_none_or(|m| m == method)
+            && path.starts_with(&self.path_prefix)
+    }
+}
+
+#[derive(Debug, Default)]
+struct MockState {
+    ray_version: String,
+    jobs: JobTable,
+    packages: HashMap<String, usize>,
+    cluster_status: ClusterStatusData,
+    failures: Vec<InjectedFailure>,
+    /// Most jobs the state API returns, whatever the requested limit
+    state_api_max_limit: Option<usize>,
+}
+
+/// In-process mock of the Ray dashboard for tests.
+///
+/// Implements the jobs, packages, cluster status, version and health endpoints with in-memory state.
+/// The server runs on the current Tokio runtime and stops when the mock is dropped.
+pub struct MockRayDashboard {
+    address: SocketAddr,
+    state: Arc<Mutex<MockState>>,
+    server: JoinHandle<()>,
+}
+
+impl std::fmt::Debug for MockRayDashboard {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("MockRayDashboard")
+            .field("address", &self.address)
+            .finish_non_exhaustive()
+    }
+}
+
+impl MockRayDashboard {
+    /// Start a mock dashboard on a random local port.
+    pub async fn start() -> std::io::Result<Self> {
+        let state = Arc::new(Mutex::new(MockState {
+            ray_version: MOCK_RAY_VERSION.to_string(),
+            ..Default::default()
+        }));
+
+        let app = Router::new()
+            .route("/api/version", get(get_version))
+            .route("/api/gcs_healthz", get(healthz))
+            .route("/api/local_raylet_healthz", get(healthz))
+            .route("/api/cluster_status", get(get_cluster_status))
+            .route("/api/jobs/", get(list_jobs).post(submit_job))
+            .route("/api/v0/jobs", get(list_jobs_state_api))
+            .route("/api/jobs/{id}", get(get_job).delete(delete_job))
+            .route("/api/jobs/{id}/stop", post(stop_job))
+            .route("/api/jobs/{id}/logs", get(get_job_logs))
+            .route(
+                "/api/packages/{protocol}/{name}",
+                get(get_package).put(put_package),
+            )
+            .layer(middleware::from_fn_with_state(state.clone(), inject_failures))
+            .with_state(state.clone());
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+        let address = listener.local_addr()?;
+        let server = tokio::spawn(async move {
+            if let Err(e) = axum::serve(listener, app).await {
+                tracing::warn!("Mock Ray dashboard stopped: {}", e);
+            }
+        });
+
+        Ok(Self {
+            address,
+            state,
+            server,
+        })
+    }
+
+    /// Base URL of the mock dashboard.
+    pub fn url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// Create a client connected to the mock dashboard.
+    pub fn client(&self) -> RayDashboardClient {
+        RayDashboardClient::new(&self.url()).expect("Mock dashboard URL is valid")
+    }
+
+    /// Set the Ray version reported by the mock dashboard.
+    pub fn set_ray_version(&self, ray_version: impl Into<String>) {
+        self.state().ray_version = ray_version.into();
+    }
+
+    /// Cap the number of jobs the state API returns, below the limit requested by the client.
+    pub fn set_state_api_max_limit(&self, max_limit: usize) {
+        self.state().state_api_max_limit = Some(max_limit);
+    }
+
+    /// Set the script used for jobs submitted without a specific script.
+    pub fn set_default_script(&self, script: JobScript) {
+        self.state().jobs.set_default_script(script);
+    }
+
+    /// Set the script used for the job submitted with the given submission ID.
+    pub fn script_job(&self, submission_id: impl Into<String>, script: JobScript) {
+        self.state().jobs.script_job(submission_id.into(), script);
+    }
+
+    /// Force a job into a status.
+    pub fn set_job_status(&self, submission_id: &str, status: JobStatus) {
+        let _ = self.state().jobs.set_status(submission_id, status);
+    }
+
+    /// Advance a job to the next status of its script.
+    pub fn advance_job(&self, submission_id: &str) {
+        let _ = self.state().jobs.advance(submission_id);
+    }
+
+    /// Append output to the logs of a job.
+    pub fn append_logs(&self, submission_id: &str, logs: &str) {
+        let _ = self.state().jobs.append_logs(submission_id, logs);
+    }
+
+    /// Set the used and total amount of a resource reported by the cluster status endpoint.
+    pub fn set_resource_usage(&self, resource: impl Into<String>, used: f64, total: f64) {
+        self.state()
+            .cluster_status
+            .cluster_status
+            .get_or_insert_default()
+            .load_metrics_report
+            .get_or_insert_default()
+            .usage
+            .insert(resource.into(), (used, total));
+    }
+
+    /// Fail matching requests until the failure is exhausted or cleared.
+    pub fn inject_failure(&self, failure: InjectedFailure) {
+        self.state().failures.push(failure);
+    }
+
+    /// Remove all injected failures.
+    pub fn clear_failures(&self) {
+        self.state().failures.clear();
+    }
+
+    /// Details of all jobs known to the mock dashboard.
+    pub fn jobs(&self) -> Vec<JobDetails> {
+        self.state().jobs.list()
+    }
+
+    /// Details of a single job.
+    pub fn job(&self, submission_id: &str) -> Option<JobDetails> {
+        self.state().jobs.get(submission_id)
+    }
+
+    /// URIs and sizes of all uploaded packages.
+    pub fn packages(&self) -> HashMap<String, usize> {
+        self.state().packages.clone()
+    }
+
+    fn state(&self) -> MutexGuard<'_, MockState> {
+        self.state.lock().expect("Mock dashboard state is not poisoned")
+    }
+}
+
+impl Drop for MockRayDashboard {
+    fn drop(&mut self) {
+        self.server.abort();
+    }
+}
+
+type SharedState = Arc<Mutex<MockState>>;
+
+fn lock(state: &SharedState) -> MutexGuard<'_, MockState> {
+    state.lock().expect("Mock dashboard state is not poisoned")
+}
+
+fn job_error(error: JobTableError) -> Response {
+    (error.status(), error.to_string()).into_response()
+}
+
+async fn inject_failures(State(state): State<SharedState>, request: Request, next: Next) -> Response {
+    let failure = {
+        let mut state = lock(&state);
+        let method = request.method().clone();
+        let path = request.uri().path().to_string();
+
+        let index = state
+            .failures
+            .iter()
+            .position(|failure| failure.matches(&method, &path));
+        index.map(|index| {
+            let failure = state.failures[index].clone();
+            if let Some(ref mut times) = state.failures[index].times {
+                *times = times.saturating_sub(1);
+                if *times == 0 {
+                    state.failures.remove(index);
+                }
+            }
+            failure
+        })
+    };
+
+    match failure {
+        Some(failure) => (failure.status, failure.body).into_response(),
+        None => next.run(request).await,
+    }
+}
+
+async fn get_version(State(state): State<SharedState>) -> Json<RayVersionResponse> {
+    let state = lock(&state);
+    Json(RayVersionResponse {
+        version: "4".to_string(),
+        ray_version: state.ray_version.clone(),
+        ray_commit: "mock".to_string(),
+    })
+}
+
+async fn get_cluster_status(State(state): State<SharedState>) -> Json<DashboardResponse<ClusterStatusData>> {
+    Json(DashboardResponse {
+        result: true,
+        msg: "Got cluster status.".to_string(),
+        data: lock(&state).cluster_status.clone(),
+    })
+}
+
+async fn healthz() -> &'static str {
+    "success"
+}
+
+async fn submit_job(State(state): State<SharedState>, Json(payload): Json<JobSubmitRequest>) -> Response {
+    match lock(&state).jobs.submit(payload) {
+        Ok(submission_id) => Json(JobSubmitResponse { submission_id }).into_response(),
+        Err(e) => job_error(e),
+    }
+}
+
+async fn list_jobs(State(state): State<SharedState>) -> Json<Vec<serde_json::Value>> {
+    Json(lock(&state).jobs.list().iter().map(ray_job).collect())
+}
+
+/// State API job listing supporting `=`/`!=` filters on `status`, `type` and `submission_id`,
+/// and a `limit` applied after filtering.
+async fn list_jobs_state_api(
+    State(state): State<SharedState>,
+    Query(params): Query<Vec<(String, String)>>,
+) -> Response {
+    let values = |name: &'static str| {
+        params
+            .iter()
+            .filter(move |(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    };
+    let limit = values("limit")
+        .next()
+        .and_then(|limit| limit.parse::<usize>().ok())
+        .unwrap_or(100);
+
+    let (mut jobs, max_limit) = {
+        let state = lock(&state);
+        (state.jobs.list(), state.state_api_max_limit)
+    };
+    let total = jobs.len();
+
+    for ((key, predicate), value) in values("filter_keys")
+        .zip(values("filter_predicates"))
+        .zip(values("filter_values"))
+    {
+        let field = |job: &JobDetails| match key {
+            "status" => Some(format!("{:?}", job.status)),
+            "type" => Some(format!("{:?}", job.job_type)),
+            "submission_id" => Some(job.submission_id.clone().unwrap_or_default()),
+            _ => None,
+        };
+        if jobs.first().is_some_and(|job| field(job).is_none()) {
+            return (StatusCode::BAD_REQUEST, format!("{} is not filterable", key)).into_response();
+        }
+        jobs.retain(|job| (field(job).as_deref() == Some(value)) == (predicate == "="));
+    }
+    let num_filtered = jobs.len();
+    jobs.truncate(max_limit.map_or(limit, |max_limit| limit.min(max_limit)));
+
+    Json(DashboardResponse {
+        result: true,
+        msg: String::new(),
+        data: StateListData {
+            result: StateListResult {
+                total,
+                num_after_truncation: total,
+                num_filtered: Some(num_filtered),
+                result: jobs.iter().map(ray_job).collect(),
+            },
+        },
+    })
+    .into_response()
+}
+
+/// Job details as Ray serializes them, with the job type under `type`.
+fn ray_job(details: &JobDetails) -> serde_json::Value {
+    let mut value = serde_json::to_value(details).expect("Job details serialize to JSON");
+    if let Some(job) = value.as_object_mut()
+        && let Some(job_type) = job.remove("job_type")
+    {
+        job.insert("type".to_string(), job_type);
+    }
+    value
+}
+
+async fn get_job(State(state): State<SharedState>, Path(submission_id): Path<String>) -> Response {
+    match lock(&state).jobs.poll(&submission_id) {
+        Ok(details) => Json(ray_job(&details)).into_response(),
+        Err(e) => job_error(e),
+    }
+}
+
+async fn delete_job(State(state): State<SharedState>, Path(submission_id): Path<String>) -> Response {
+    match lock(&state).jobs.delete(&submission_id) {
+        Ok(()) => Json(JobDeleteResponse { deleted: true }).into_response(),
+        Err(e) => job_error(e),
+    }
+}
+
+async fn stop_job(State(state): State<SharedState>, Path(submission_id): Path<String>) -> Response {
+    match lock(&state).jobs.stop(&submission_id) {
+        Ok(stopped) => Json(JobStopResponse { stopped }).into_response(),
+        Err(e) => job_error(e),
+    }
+}
+
+async fn get_job_logs(State(state): State<SharedState>, Path(submission_id): Path<String>) -> Response {
+    match lock(&state).jobs.logs(&submission_id) {
+        Ok(logs) => Json(JobLogsResponse { logs }).into_response(),
+        Err(e) => job_error(e),
+    }
+}
+
+async fn get_package(
+    State(state): State<SharedState>,
+    Path((protocol, name)): Path<(String, String)>,
+) -> StatusCode {
+    let state = lock(&state);
+    match state.packages.contains_key(&format!("{}://{}", protocol, name)) {
+        true => StatusCode::OK,
+        false => StatusCode::NOT_FOUND,
+    }
+}
+
+async fn put_package(
+    State(state): State<SharedState>,
+    Path((protocol, name)): Path<(String, String)>,
+    body: Bytes,
+) -> StatusCode {
+    let mut state = lock(&state);
+    state
+        .packages
+        .insert(format!("{}://{}", protocol, name), body.len());
+    StatusCode::OK
+}
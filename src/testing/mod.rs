@@ -0,0 +1,13 @@
+//! Test utilities for crates depending on the SDK.
+//!
+//! Enabled with the `testing` feature.
+
+mod fake;
+mod fixtures;
+mod mock;
+mod script;
+
+pub use fake::FakeRayDashboard;
+pub use fixtures::{FIXTURE_MODE_ENV, FixtureFile, FixtureMode, HttpFixture, RecordedExchange};
+pub use mock::{InjectedFailure, MOCK_RAY_VERSION, MockRayDashboard};
+pub use script::JobScript;
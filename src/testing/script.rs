@@ -0,0 +1,306 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::schemas::jobs::{JobDetails, JobDriverInfo, JobStatus, JobSubmitRequest, JobType};
+
+/// Scripted lifecycle of a job in `MockRayDashboard` or `FakeRayDashboard`.
+///
+/// The job starts in the first status and advances one status every time its details are
+/// fetched, staying in the last status once reached.
+#[derive(Debug, Clone)]
+pub struct JobScript {
+    pub statuses: Vec<JobStatus>,
+    pub logs: String,
+    pub message: Option<String>,
+    pub error_type: Option<String>,
+    pub exit_code: Option<i32>,
+}
+
+impl Default for JobScript {
+    fn default() -> Self {
+        Self::new([JobStatus::PENDING, JobStatus::RUNNING, JobStatus::SUCCEEDED])
+    }
+}
+
+impl JobScript {
+    /// Create a new JobScript moving through the given statuses.
+    pub fn new(statuses: impl IntoIterator<Item = JobStatus>) -> Self {
+        let statuses: Vec<JobStatus> = statuses.into_iter().collect();
+        let exit_code = match statuses.last() {
+            Some(JobStatus::SUCCEEDED) => Some(0),
+            Some(JobStatus::FAILED) => Some(1),
+            _ => None,
+        };
+        Self {
+            statuses,
+            logs: String::new(),
+            message: None,
+            error_type: None,
+            exit_code,
+        }
+    }
+
+    /// A job that keeps running until it is stopped.
+    pub fn running() -> Self {
+        Self::new([JobStatus::PENDING, JobStatus::RUNNING])
+    }
+
+    /// A job that fails after running.
+    pub fn failing() -> Self {
+        Self::new([JobStatus::PENDING, JobStatus::RUNNING, JobStatus::FAILED])
+    }
+
+    /// Set the log output of the job.
+    pub fn with_logs(mut self, logs: impl Into<String>) -> Self {
+        self.logs = logs.into();
+        self
+    }
+
+    /// Set the message reported once the job reaches its final status.
+    pub fn with_message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+
+    /// Set the error type reported once the job reaches its final status.
+    pub fn with_error_type(mut self, error_type: impl Into<String>) -> Self {
+        self.error_type = Some(error_type.into());
+        self
+    }
+
+    /// Set the driver exit code reported once the job reaches a terminal status.
+    pub fn with_exit_code(mut self, exit_code: i32) -> Self {
+        self.exit_code = Some(exit_code);
+        self
+    }
+}
+
+#[derive(Debug)]
+struct ScriptedJob {
+    details: JobDetails,
+    /// Driver reported once the job has started, like Ray does
+    driver_info: JobDriverInfo,
+    script: JobScript,
+    step: usize,
+}
+
+impl ScriptedJob {
+    fn set_status(&mut self, status: JobStatus) {
+        self.details.status = status;
+        if status == JobStatus::RUNNING {
+            self.details
+                .driver_info
+                .get_or_insert_with(|| self.driver_info.clone());
+        }
+        if status.is_terminal() {
+            self.details.end_time.get_or_insert_with(now_millis);
+            if status == self.script.statuses.last().copied().unwrap_or(status) {
+                self.details.message = self.script.message.clone();
+                self.details.error_type = self.script.error_type.clone();
+                self.details.driver_exit_code = self.script.exit_code;
+            }
+        }
+    }
+
+    fn advance(&mut self) {
+        if self.details.status.is_terminal() {
+            return;
+        }
+        if self.step + 1 < self.script.statuses.len() {
+            self.step += 1;
+            self.set_status(self.script.statuses[self.step]);
+        }
+    }
+}
+
+/// Errors returned by `JobTable`, mirroring the dashboard's job errors.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum JobTableError {
+    NotFound(String),
+    AlreadyExists(String),
+    NotTerminal(String),
+}
+
+impl fmt::Display for JobTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JobTableError::NotFound(id) => write!(f, "Job {} does not exist", id),
+            JobTableError::AlreadyExists(id) => write!(f, "Job with submission_id {} already exists", id),
+            JobTableError::NotTerminal(id) => {
+                write!(f, "Job {} is not in a terminal state and cannot be deleted", id)
+            }
+        }
+    }
+}
+
+impl JobTableError {
+    /// Status code the dashboard responds with.
+    pub(crate) fn status(&self) -> http::StatusCode {
+        match self {
+            JobTableError::NotFound(_) => http::StatusCode::NOT_FOUND,
+            _ => http::StatusCode::BAD_REQUEST,
+        }
+    }
+}
+
+/// The error the client returns for the dashboard's response, so callers can match on its status.
+impl From<JobTableError> for crate::Error {
+    fn from(error: JobTableError) -> Self {
+        let response = http::Response::builder()
+            .status(error.status())
+            .body(error.to_string())
+            .expect("Error response is valid");
+        match reqwest::Response::from(response).error_for_status() {
+            Err(e) => crate::Error::Request(e),
+            Ok(_) => unreachable!("Job table errors have an error status"),
+        }
+    }
+}
+
+/// In-memory jobs shared by the mock and fake dashboards.
+#[derive(Debug, Default)]
+pub(crate) struct JobTable {
+    jobs: BTreeMap<String, ScriptedJob>,
+    scripts: HashMap<String, JobScript>,
+    default_script: JobScript,
+    next_id: u64,
+    next_driver: u64,
+}
+
+impl JobTable {
+    pub(crate) fn set_default_script(&mut self, script: JobScript) {
+        self.default_script = script;
+    }
+
+    pub(crate) fn script_job(&mut self, submission_id: String, script: JobScript) {
+        self.scripts.insert(submission_id, script);
+    }
+
+    pub(crate) fn submit(&mut self, payload: JobSubmitRequest) -> Result<String, JobTableError> {
+        let submission_id = match payload.submission_id {
+            Some(ref submission_id) => submission_id.clone(),
+            None => {
+                self.next_id += 1;
+                format!("raysubmit_mock{}", self.next_id)
+            }
+        };
+        if self.jobs.contains_key(&submission_id) {
+            return Err(JobTableError::AlreadyExists(submission_id));
+        }
+
+        let script = self
+            .scripts
+            .remove(&submission_id)
+            .unwrap_or_else(|| self.default_script.clone());
+        let details = JobDetails {
+            job_type: JobType::SUBMISSION,
+            entrypoint: payload.entrypoint,
+            status: JobStatus::PENDING,
+            submission_id: Some(submission_id.clone()),
+            driver_info: None,
+            message: None,
+            error_type: None,
+            start_time: Some(now_millis()),
+            end_time: None,
+            metadata: payload.metadata,
+            runtime_env: payload.runtime_env,
+            driver_agent_http_address: None,
+            driver_node_id: None,
+            driver_exit_code: None,
+            entrypoint_num_cpus: payload.entrypoint_num_cpus,
+            entrypoint_num_gpus: payload.entrypoint_num_gpus,
+            entrypoint_memory: payload.entrypoint_memory,
+            entrypoint_resources: payload.entrypoint_resources,
+        };
+
+        self.next_driver += 1;
+        let driver_info = JobDriverInfo {
+            id: format!("{:08x}", self.next_driver),
+            node_ip_address: "127.0.0.1".to_string(),
+            pid: (10_000 + self.next_driver).to_string(),
+        };
+        let mut job = ScriptedJob {
+            details,
+            driver_info,
+            script,
+            step: 0,
+        };
+        if let Some(status) = job.script.statuses.first().copied() {
+            job.set_status(status);
+        }
+        self.jobs.insert(submission_id.clone(), job);
+        Ok(submission_id)
+    }
+
+    pub(crate) fn list(&self) -> Vec<JobDetails> {
+        self.jobs.values().map(|job| job.details.clone()).collect()
+    }
+
+    pub(crate) fn get(&self, submission_id: &str) -> Option<JobDetails> {
+        self.jobs.get(submission_id).map(|job| job.details.clone())
+    }
+
+    /// Return the current details of a job and advance it to the next scripted status.
+    pub(crate) fn poll(&mut self, submission_id: &str) -> Result<JobDetails, JobTableError> {
+        let job = self.job_mut(submission_id)?;
+        let details = job.details.clone();
+        job.advance();
+        Ok(details)
+    }
+
+    pub(crate) fn set_status(&mut self, submission_id: &str, status: JobStatus) -> Result<(), JobTableError> {
+        self.job_mut(submission_id)?.set_status(status);
+        Ok(())
+    }
+
+    pub(crate) fn advance(&mut self, submission_id: &str) -> Result<(), JobTableError> {
+        self.job_mut(submission_id)?.advance();
+        Ok(())
+    }
+
+    pub(crate) fn append_logs(&mut self, submission_id: &str, logs: &str) -> Result<(), JobTableError> {
+        self.job_mut(submission_id)?.script.logs.push_str(logs);
+        Ok(())
+    }
+
+    pub(crate) fn logs(&self, submission_id: &str) -> Result<String, JobTableError> {
+        self.jobs
+            .get(submission_id)
+            .map(|job| job.script.logs.clone())
+            .ok_or_else(|| JobTableError::NotFound(submission_id.to_string()))
+    }
+
+    /// Stop a job, returning whether it was still running.
+    pub(crate) fn stop(&mut self, submission_id: &str) -> Result<bool, JobTableError> {
+        let job = self.job_mut(submission_id)?;
+        let stopped = !job.details.status.is_terminal();
+        if stopped {
+            job.set_status(JobStatus::STOPPED);
+        }
+        Ok(stopped)
+    }
+
+    pub(crate) fn delete(&mut self, submission_id: &str) -> Result<(), JobTableError> {
+        if !self.job_mut(submission_id)?.details.status.is_terminal() {
+            return Err(JobTableError::NotTerminal(submission_id.to_string()));
+        }
+        self.jobs.remove(submission_id);
+        Ok(())
+    }
+
+    fn job_mut(&mut self, submission_id: &str) -> Result<&mut ScriptedJob, JobTableError> {
+        self.jobs
+            .get_mut(submission_id)
+            .ok_or_else(|| JobTableError::NotFound(submission_id.to_string()))
+    }
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_millis() as u64)
+        .unwrap_or_default()
+}
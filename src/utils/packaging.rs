@@ -63,6 +63,18 @@ pub fn create_package(source_dir: &Path, output_path: &Path) -> crate::Result<()
     Ok(())
 }
 
+/// Create a zip package from a directory in a temporary file and return its contents
+/// Always respects .gitignore files
+pub fn package_directory(directory: &Path) -> crate::Result<Vec<u8>> {
+    let temp_file = tempfile::Builder::new()
+        .prefix("ray_pkg_")
+        .suffix(".zip")
+        .tempfile()
+        .map_err(|e| crate::Error::Generic(format!("Failed to create temp file: {}", e)))?;
+    create_package(directory, temp_file.path())?;
+    Ok(std::fs::read(temp_file.path())?)
+}
+
 /// Compute a hash of a directory's contents
 /// Always respects .gitignore files
 pub fn hash_directory(directory: &Path) -> crate::Result<String> {
@@ -0,0 +1,51 @@
+mod common;
+
+use std::time::Duration;
+
+use ray_dashboard_sdk::{
+    RunOptions,
+    blocking::RayDashboardClient,
+    schemas::jobs::{JobStatus, JobSubmitRequest},
+};
+
+fn random_submission_id() -> String {
+    let id = uuid::Uuid::new_v4().to_string();
+    format!("test-{}", id)
+}
+
+#[test]
+fn test_blocking_ping() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+    client.ping().expect("Able to ping");
+}
+
+#[test]
+fn test_blocking_run_job() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let submission_id = random_submission_id();
+    let payload = JobSubmitRequest::new("echo 'ABC123'").with_submission_id(&submission_id);
+    let options = RunOptions::new().with_timeout(Duration::from_secs(30));
+
+    let outcome = client
+        .run_job(&payload, options)
+        .expect("Able to run job")
+        .into_result()
+        .expect("Job succeeded");
+    assert_eq!(outcome.status(), JobStatus::SUCCEEDED);
+    assert!(outcome.logs.unwrap().contains("ABC123"));
+
+    let status = client.get_job_status(&submission_id).unwrap();
+    assert_eq!(status, JobStatus::SUCCEEDED);
+}
+
+#[test]
+fn test_blocking_upload_directory() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    std::fs::write(temp_dir.path().join("file1.txt"), "content1").unwrap();
+
+    let package_uri = client.upload_directory_if_needed(temp_dir.path()).unwrap();
+    assert!(client.package_exists(&package_uri).unwrap());
+}
@@ -1,5 +1,7 @@
 mod common;
 
+use std::time::Duration;
+
 use ray_dashboard_sdk::RayDashboardClient;
 
 #[tokio::test]
@@ -14,3 +16,57 @@ async fn test_get_version() {
     let version = client.get_version().await.expect("Able to get version");
     assert_eq!(version.ray_version, "2.50.1");
 }
+
+#[tokio::test]
+async fn test_health() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+    let health = client.health().await.expect("Able to check health");
+    assert!(health.is_healthy(), "{:?}", health.unhealthy_reason());
+}
+
+#[tokio::test]
+async fn test_wait_until_ready() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+    client
+        .wait_until_ready(Duration::from_secs(10))
+        .await
+        .expect("Cluster is ready");
+}
+
+#[tokio::test]
+async fn test_wait_until_ready_unreachable() {
+    let client = RayDashboardClient::new("http://127.0.0.1:1").unwrap();
+    let error = client
+        .wait_until_ready(Duration::from_millis(100))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ray_dashboard_sdk::Error::ClusterNotReady { .. }));
+}
+
+#[tokio::test]
+async fn test_wait_until_ready_hung_connection() {
+    // Connections are accepted by the kernel but never answered
+    let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    let client = RayDashboardClient::new(&url).unwrap();
+
+    let start = std::time::Instant::now();
+    let error = client
+        .wait_until_ready(Duration::from_millis(200))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, ray_dashboard_sdk::Error::ClusterNotReady { .. }));
+    assert!(start.elapsed() < Duration::from_secs(2));
+}
+
+#[tokio::test]
+async fn test_server_capabilities() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+    let capabilities = client
+        .server_capabilities()
+        .await
+        .expect("Able to get capabilities");
+    assert_eq!(capabilities.version.to_string(), "2.50.1");
+    assert!(capabilities.uv_runtime_env);
+    assert!(capabilities.state_api);
+}
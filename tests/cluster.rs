@@ -0,0 +1,24 @@
+mod common;
+
+use ray_dashboard_sdk::{ClusterAPI, RayDashboardClient, schemas::jobs::JobSubmitRequest};
+
+#[tokio::test]
+async fn test_get_cluster_status() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+    let status = client
+        .get_cluster_status()
+        .await
+        .expect("Able to get cluster status");
+    assert!(status.resources.total("CPU") > 0.0);
+}
+
+#[tokio::test]
+async fn test_can_schedule() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let payload = JobSubmitRequest::new("echo hello").with_entrypoint_num_cpus(0.1);
+    assert!(client.can_schedule(&payload).await.unwrap());
+
+    let payload = JobSubmitRequest::new("echo hello").with_entrypoint_num_gpus(1000.0);
+    assert!(!client.can_schedule(&payload).await.unwrap());
+}
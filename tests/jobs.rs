@@ -1,12 +1,18 @@
 mod common;
 
-use std::time::Duration;
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
 
+use futures::StreamExt;
 use ray_dashboard_sdk::{
-    JobSubmissionAPI, RayDashboardClient,
+    Error, JobSubmissionAPI, RayDashboardClient, RunOptions, WaitOptions,
+    client::bulk::{BulkJobOutcome, JobSelector},
     schemas::{
         env::RuntimeEnv,
         jobs::{JobStatus, JobSubmitRequest},
+        query::{JobQuery, JobSortKey, SortOrder},
     },
 };
 
@@ -138,3 +144,208 @@ async fn test_submit_job_with_working_dir() {
             .any(|line| line.contains("Hello from working directory!"))
     );
 }
+
+#[tokio::test]
+async fn test_run_job() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let submission_id = random_submission_id();
+    let payload = JobSubmitRequest::new("echo 'ABC123'").with_submission_id(&submission_id);
+    let options = RunOptions::new().with_timeout(Duration::from_secs(30));
+
+    let outcome = client
+        .run_job(&payload, options)
+        .await
+        .expect("Able to run job")
+        .into_result()
+        .expect("Job succeeded");
+
+    assert_eq!(outcome.submission_id, submission_id);
+    assert_eq!(outcome.status(), JobStatus::SUCCEEDED);
+    assert!(outcome.logs.unwrap().contains("ABC123"));
+}
+
+#[tokio::test]
+async fn test_run_job_timeout_stops_job() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let submission_id = random_submission_id();
+    let payload = JobSubmitRequest::new("sleep 60").with_submission_id(&submission_id);
+    let options = RunOptions::new()
+        .with_timeout(Duration::from_secs(1))
+        .with_fetch_logs(false);
+
+    let outcome = client.run_job(&payload, options).await.expect("Able to run job");
+    assert!(outcome.timed_out);
+    assert_eq!(outcome.status(), JobStatus::STOPPED);
+
+    let error = outcome.into_result().unwrap_err();
+    assert!(matches!(error, Error::Timeout { .. }));
+}
+
+#[tokio::test]
+async fn test_wait_for_terminal_with_options() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let submission_id = random_submission_id();
+    let payload = JobSubmitRequest::new("echo 'ABC123'").with_submission_id(&submission_id);
+    client.submit_job(&payload).await.expect("Able to submit job");
+
+    let statuses = Arc::new(Mutex::new(Vec::new()));
+    let observed = statuses.clone();
+    let options = WaitOptions::new()
+        .with_initial_interval(Duration::from_millis(100))
+        .with_timeout(Duration::from_secs(30))
+        .with_on_status_change(move |_, details| observed.lock().unwrap().push(details.status));
+
+    let details = client
+        .wait_for_terminal_with_options(&submission_id, &options)
+        .await
+        .expect("Job reaches terminal state");
+    assert_eq!(details.status, JobStatus::SUCCEEDED);
+
+    let statuses = statuses.lock().unwrap();
+    assert_eq!(statuses.last(), Some(&JobStatus::SUCCEEDED));
+}
+
+#[tokio::test]
+async fn test_wait_for_terminal_timeout() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let submission_id = random_submission_id();
+    let payload = JobSubmitRequest::new("sleep 60").with_submission_id(&submission_id);
+    client.submit_job(&payload).await.expect("Able to submit job");
+
+    let error = client
+        .wait_for_terminal(&submission_id, Some(Duration::from_secs(1)))
+        .await
+        .unwrap_err();
+    assert!(matches!(error, Error::Timeout { submission_id: ref id, .. } if id == &submission_id));
+
+    client.stop_job(&submission_id).await.expect("Able to stop job");
+}
+
+#[tokio::test]
+async fn test_watch_job() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let submission_id = random_submission_id();
+    let payload = JobSubmitRequest::new("echo 'ABC123'").with_submission_id(&submission_id);
+    client.submit_job(&payload).await.expect("Able to submit job");
+
+    let events = client
+        .watch_job(&submission_id, Duration::from_millis(100))
+        .collect::<Vec<_>>()
+        .await;
+
+    let statuses = events
+        .into_iter()
+        .map(|event| event.expect("Able to watch job").status)
+        .collect::<Vec<_>>();
+    assert_eq!(statuses.last(), Some(&JobStatus::SUCCEEDED));
+}
+
+#[tokio::test]
+async fn test_job_handle_stop_on_cancellation() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let submission_id = random_submission_id();
+    let payload = JobSubmitRequest::new("sleep 60").with_submission_id(&submission_id);
+    let token = tokio_util::sync::CancellationToken::new();
+    let handle = client
+        .submit_job_handle(&payload)
+        .await
+        .expect("Able to submit job")
+        .with_cancellation_token(token.clone());
+
+    token.cancel();
+    let error = handle.wait(&WaitOptions::new()).await.unwrap_err();
+    assert!(matches!(error, Error::Cancelled { .. }));
+
+    let details = client
+        .wait_for_terminal(&submission_id, Some(Duration::from_secs(10)))
+        .await
+        .expect("Job stops after cancellation");
+    assert_eq!(details.status, JobStatus::STOPPED);
+}
+
+#[tokio::test]
+async fn test_job_handle_stop_on_drop() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let submission_id = random_submission_id();
+    let payload = JobSubmitRequest::new("sleep 60").with_submission_id(&submission_id);
+    let handle = client
+        .submit_job_handle(&payload)
+        .await
+        .expect("Able to submit job")
+        .with_stop_on_drop(true);
+    drop(handle);
+
+    let details = client
+        .wait_for_terminal(&submission_id, Some(Duration::from_secs(10)))
+        .await
+        .expect("Job stops after the handle is dropped");
+    assert_eq!(details.status, JobStatus::STOPPED);
+}
+
+#[tokio::test]
+async fn test_query_jobs() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let submission_id = random_submission_id();
+    let payload = JobSubmitRequest::new("echo 'ABC123'")
+        .with_submission_id(&submission_id)
+        .with_metadata_item("query_test", &submission_id);
+    client.submit_job(&payload).await.expect("Able to submit job");
+
+    let query = JobQuery::new()
+        .with_metadata_item("query_test", &submission_id)
+        .with_submission_id_prefix("test-")
+        .with_sort(JobSortKey::StartTime, SortOrder::Descending)
+        .with_limit(10);
+    let jobs = client.query_jobs(&query).await.expect("Able to query jobs");
+
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].submission_id, Some(submission_id));
+}
+
+#[tokio::test]
+async fn test_stop_and_delete_jobs() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let sweep_id = random_submission_id();
+    let mut submission_ids = Vec::new();
+    for _ in 0..3 {
+        let submission_id = random_submission_id();
+        let payload = JobSubmitRequest::new("sleep 60")
+            .with_submission_id(&submission_id)
+            .with_metadata_item("sweep", &sweep_id);
+        client.submit_job(&payload).await.expect("Able to submit job");
+        submission_ids.push(submission_id);
+    }
+
+    let selector = JobSelector::Query(JobQuery::new().with_metadata_item("sweep", &sweep_id));
+    let report = client.stop_jobs(&selector, 2).await.expect("Able to stop jobs");
+    assert!(report.is_success());
+    assert_eq!(report.succeeded().count(), 3);
+
+    for submission_id in &submission_ids {
+        client
+            .wait_for_terminal(submission_id, Some(Duration::from_secs(10)))
+            .await
+            .unwrap();
+    }
+
+    let selector = JobSelector::Ids(submission_ids);
+    let report = client
+        .delete_jobs(&selector, 2)
+        .await
+        .expect("Able to delete jobs");
+    assert!(
+        report
+            .results
+            .iter()
+            .all(|result| matches!(result.outcome, BulkJobOutcome::Deleted))
+    );
+}
@@ -0,0 +1,47 @@
+mod common;
+
+use std::time::Duration;
+
+use ray_dashboard_sdk::{
+    JobSubmissionAPI, LogsAPI, RayDashboardClient,
+    schemas::{jobs::JobSubmitRequest, logs::LogQuery},
+};
+
+fn random_submission_id() -> String {
+    let id = uuid::Uuid::new_v4().to_string();
+    format!("test-{}", id)
+}
+
+#[tokio::test]
+async fn test_get_driver_logs() {
+    let client = RayDashboardClient::new(common::RAY_DASHBOARD_URL).unwrap();
+
+    let submission_id = random_submission_id();
+    let payload = JobSubmitRequest::new("echo 'ABC123'").with_submission_id(&submission_id);
+    client.submit_job(&payload).await.expect("Able to submit job");
+
+    let details = client
+        .wait_for_terminal(&submission_id, Some(Duration::from_secs(30)))
+        .await
+        .unwrap();
+
+    let logs = client
+        .get_driver_logs(&details, None)
+        .await
+        .expect("Able to get driver logs");
+    assert!(logs.contains("ABC123"));
+
+    let node_id = details.driver_node_id.expect("Job has a driver node");
+    let listing = client
+        .list_logs(&node_id, Some("job-driver-*"))
+        .await
+        .expect("Able to list logs");
+    let filename = format!("job-driver-{}.log", submission_id);
+    assert!(listing.files().any(|file| file == filename));
+
+    let tail = client
+        .get_log(&LogQuery::for_file(&node_id, &filename).with_lines(1))
+        .await
+        .expect("Able to tail log");
+    assert!(tail.lines().count() <= 1);
+}
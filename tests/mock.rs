@@ -0,0 +1,442 @@
+use std::{
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use axum::http::{Method, StatusCode};
+use futures::StreamExt;
+use ray_dashboard_sdk::{
+    Error, JobRetryPolicy, JobSubmissionAPI, MetricsRecorder, PackagesAPI, RequestMetrics, RetryReport,
+    RunOptions, SubmissionOutcome, WaitOptions,
+    client::bulk::JobSelector,
+    schemas::{
+        jobs::{JobStatus, JobSubmitRequest},
+        query::JobQuery,
+    },
+    testing::{InjectedFailure, JobScript, MockRayDashboard},
+};
+use tokio_util::sync::CancellationToken;
+
+fn wait_options() -> WaitOptions {
+    WaitOptions::new()
+        .with_initial_interval(Duration::from_millis(10))
+        .with_timeout(Duration::from_secs(5))
+}
+
+#[tokio::test]
+async fn test_mock_run_job() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.script_job("mock-job", JobScript::default().with_logs("ABC123\n"));
+    let client = mock.client();
+
+    let payload = JobSubmitRequest::new("echo 'ABC123'").with_submission_id("mock-job");
+    let options = RunOptions::new().with_wait_options(wait_options());
+
+    let outcome = client
+        .run_job(&payload, options)
+        .await
+        .expect("Able to run job")
+        .into_result()
+        .expect("Job succeeded");
+
+    assert_eq!(outcome.status(), JobStatus::SUCCEEDED);
+    assert_eq!(outcome.exit_code(), Some(0));
+    assert!(outcome.logs.unwrap().contains("ABC123"));
+}
+
+#[tokio::test]
+async fn test_mock_failed_job() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.set_default_script(JobScript::failing().with_message("Job entrypoint command failed"));
+    let client = mock.client();
+
+    let payload = JobSubmitRequest::new("exit 1");
+    let outcome = client
+        .run_job(&payload, RunOptions::new().with_wait_options(wait_options()))
+        .await
+        .expect("Able to run job");
+
+    let error = outcome.into_result().unwrap_err();
+    assert!(matches!(
+        error,
+        Error::JobFailed {
+            status: JobStatus::FAILED,
+            ..
+        }
+    ));
+}
+
+#[tokio::test]
+async fn test_mock_run_job_stop_failure_keeps_outcome() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.set_default_script(JobScript::running());
+    mock.inject_failure(InjectedFailure::new("/api/jobs/stuck-job/stop"));
+    let client = mock.client();
+
+    let payload = JobSubmitRequest::new("sleep 60").with_submission_id("stuck-job");
+    let options = RunOptions::new()
+        .with_wait_options(wait_options())
+        .with_timeout(Duration::from_millis(50));
+    let outcome = client.run_job(&payload, options).await.expect("Able to run job");
+
+    assert!(outcome.timed_out);
+    assert_eq!(outcome.status(), JobStatus::RUNNING);
+}
+
+#[tokio::test]
+async fn test_mock_run_job_poll_error_does_not_stop_job() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.set_default_script(JobScript::running());
+    mock.inject_failure(
+        InjectedFailure::new("/api/jobs/healthy-job")
+            .with_method(Method::GET)
+            .with_times(1),
+    );
+    let client = mock.client();
+
+    let payload = JobSubmitRequest::new("sleep 60").with_submission_id("healthy-job");
+    let options = RunOptions::new()
+        .with_wait_options(wait_options())
+        .with_stop_on_cancel(true);
+    assert!(client.run_job(&payload, options).await.is_err());
+
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_ne!(mock.job("healthy-job").unwrap().status, JobStatus::STOPPED);
+}
+
+#[tokio::test]
+async fn test_mock_failure_zero_times_never_fails() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.inject_failure(InjectedFailure::new("/api/jobs/").with_times(0));
+    let client = mock.client();
+
+    let response = client
+        .submit_job(&JobSubmitRequest::new("echo 'ok'"))
+        .await
+        .expect("Submission is not failed");
+    client.get_job_details(&response.submission_id).await.unwrap();
+}
+
+#[tokio::test]
+async fn test_mock_serves_ray_wire_format() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    let client = mock.client();
+    let submission_id = client
+        .submit_job(&JobSubmitRequest::new("echo 'ok'"))
+        .await
+        .unwrap()
+        .submission_id;
+    mock.set_job_status(&submission_id, JobStatus::RUNNING);
+
+    let url = format!("{}/api/jobs/{}", mock.url(), submission_id);
+    let body: serde_json::Value = reqwest::get(url).await.unwrap().json().await.unwrap();
+    assert_eq!(body["type"], "SUBMISSION");
+    assert!(body.get("job_type").is_none());
+    assert!(body["driver_info"]["id"].is_string());
+}
+
+#[tokio::test]
+async fn test_mock_query_jobs() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.set_default_script(JobScript::running());
+    let client = mock.client();
+
+    for submission_id in ["query-1", "query-2", "query-3"] {
+        let payload = JobSubmitRequest::new("sleep 60").with_submission_id(submission_id);
+        client.submit_job(&payload).await.unwrap();
+    }
+    mock.set_job_status("query-2", JobStatus::SUCCEEDED);
+    // The state API is used instead of listing every job
+    mock.inject_failure(InjectedFailure::new("/api/jobs/").with_method(Method::GET));
+
+    let query = JobQuery::new()
+        .with_statuses([JobStatus::PENDING, JobStatus::RUNNING])
+        .with_limit(1)
+        .with_offset(1);
+    let jobs = client.query_jobs(&query).await.unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_ne!(jobs[0].submission_id.as_deref(), Some("query-2"));
+
+    // Jobs left out by the state API's limit are listed instead, which fails here
+    mock.set_state_api_max_limit(2);
+    let query = JobQuery::new().with_limit(1);
+    assert_eq!(client.query_jobs(&query).await.unwrap().len(), 1);
+    assert!(client.query_jobs(&JobQuery::new()).await.is_err());
+    mock.clear_failures();
+    assert_eq!(client.query_jobs(&JobQuery::new()).await.unwrap().len(), 3);
+
+    // Servers without the state API list every job and filter client-side
+    mock.clear_failures();
+    mock.set_ray_version("1.13.0");
+    let client = mock.client();
+    let query = JobQuery::new().with_status(JobStatus::SUCCEEDED);
+    let jobs = client.query_jobs(&query).await.unwrap();
+    assert_eq!(jobs.len(), 1);
+    assert_eq!(jobs[0].submission_id.as_deref(), Some("query-2"));
+}
+
+#[tokio::test]
+async fn test_mock_watch_job() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    let client = mock.client();
+
+    let payload = JobSubmitRequest::new("echo 'ABC123'").with_submission_id("mock-job");
+    client.submit_job(&payload).await.expect("Able to submit job");
+
+    let statuses = client
+        .watch_job("mock-job", Duration::from_millis(10))
+        .map(|event| event.expect("Able to watch job").status)
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(
+        statuses,
+        vec![JobStatus::PENDING, JobStatus::RUNNING, JobStatus::SUCCEEDED]
+    );
+}
+
+#[tokio::test]
+async fn test_mock_watch_unknown_job_ends() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    let client = mock.client();
+
+    let events = client
+        .watch_job("unknown-job", Duration::from_millis(10))
+        .collect::<Vec<_>>()
+        .await;
+    assert!(matches!(
+        events.as_slice(),
+        [Err(Error::Request(e))] if e.status() == Some(reqwest::StatusCode::NOT_FOUND)
+    ));
+}
+
+#[tokio::test]
+async fn test_mock_stop_and_delete_jobs() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.set_default_script(JobScript::running());
+    let client = mock.client();
+
+    for _ in 0..3 {
+        client
+            .submit_job(&JobSubmitRequest::new("sleep 60"))
+            .await
+            .expect("Able to submit job");
+    }
+
+    let ids = mock
+        .jobs()
+        .into_iter()
+        .filter_map(|job| job.submission_id)
+        .collect::<Vec<_>>();
+
+    let error = client.delete_job(&ids[0]).await.unwrap_err();
+    assert!(matches!(error, Error::Request(_)));
+
+    let selector = JobSelector::Ids(ids);
+
+    let report = client.stop_jobs(&selector, 2).await.expect("Able to stop jobs");
+    assert_eq!(report.succeeded().count(), 3);
+
+    let report = client
+        .delete_jobs(&selector, 2)
+        .await
+        .expect("Able to delete jobs");
+    assert!(report.is_success());
+    assert!(mock.jobs().is_empty());
+}
+
+#[tokio::test]
+async fn test_mock_injected_failure() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.inject_failure(
+        InjectedFailure::new("/api/jobs/")
+            .with_method(Method::POST)
+            .with_status(StatusCode::SERVICE_UNAVAILABLE)
+            .with_times(1),
+    );
+    let client = mock.client();
+
+    let payload = JobSubmitRequest::new("echo 'ABC123'");
+    let error = client.submit_job(&payload).await.unwrap_err();
+    assert!(matches!(
+        error,
+        Error::Request(ref e) if e.status() == Some(reqwest::StatusCode::SERVICE_UNAVAILABLE)
+    ));
+
+    client
+        .submit_job(&payload)
+        .await
+        .expect("Failure is only injected once");
+}
+
+#[tokio::test]
+async fn test_mock_packages_and_version() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.set_ray_version("2.9.0");
+    let client = mock.client();
+
+    let capabilities = client
+        .server_capabilities()
+        .await
+        .expect("Able to get capabilities");
+    assert!(!capabilities.uv_runtime_env);
+
+    let uri = "gcs://_ray_pkg_mock.zip";
+    assert!(!client.package_exists(uri).await.unwrap());
+    client
+        .upload_package(uri, b"content".to_vec())
+        .await
+        .expect("Able to upload package");
+    assert!(client.package_exists(uri).await.unwrap());
+    assert_eq!(mock.packages().get(uri), Some(&7));
+}
+
+#[derive(Debug, Default)]
+struct RecordingMetrics {
+    requests: Mutex<Vec<RequestMetrics>>,
+    submissions: Mutex<Vec<SubmissionOutcome>>,
+    retries: Mutex<Vec<u32>>,
+}
+
+impl MetricsRecorder for RecordingMetrics {
+    fn record_request(&self, metrics: &RequestMetrics) {
+        self.requests.lock().unwrap().push(metrics.clone());
+    }
+
+    fn record_job_submission(&self, outcome: SubmissionOutcome) {
+        self.submissions.lock().unwrap().push(outcome);
+    }
+
+    fn record_job_retries(&self, report: &RetryReport) {
+        self.retries.lock().unwrap().push(report.retries());
+    }
+}
+
+#[tokio::test]
+async fn test_mock_job_handle_stops_once_on_cancellation() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.set_default_script(JobScript::running());
+    let metrics = Arc::new(RecordingMetrics::default());
+    let client = mock.client().with_metrics_recorder(metrics.clone());
+
+    let token = CancellationToken::new();
+    let handle = client
+        .submit_job_handle(&JobSubmitRequest::new("sleep 60"))
+        .await
+        .unwrap()
+        .with_stop_on_drop(true)
+        .with_cancellation_token(token.clone());
+    let options = wait_options();
+    let wait = handle.wait(&options);
+    let cancel = async {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        token.cancel();
+    };
+    let (result, _) = tokio::join!(wait, cancel);
+    assert!(matches!(result, Err(Error::Cancelled { .. })));
+    drop(handle);
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let stops = metrics
+        .requests
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|request| request.endpoint.ends_with("/stop"))
+        .count();
+    assert_eq!(stops, 1);
+}
+
+#[tokio::test]
+async fn test_mock_metrics_recorder() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.inject_failure(
+        InjectedFailure::new("/api/jobs/")
+            .with_method(Method::POST)
+            .with_status(StatusCode::SERVICE_UNAVAILABLE)
+            .with_times(1),
+    );
+    let metrics = Arc::new(RecordingMetrics::default());
+    let client = mock.client().with_metrics_recorder(metrics.clone());
+
+    let payload = JobSubmitRequest::new("echo 'ABC123'").with_submission_id("metrics-job");
+    client.submit_job(&payload).await.unwrap_err();
+    client.submit_job(&payload).await.unwrap();
+    client.get_job_details("metrics-job").await.unwrap();
+    client
+        .upload_package("gcs://_ray_pkg_metrics.zip", b"content".to_vec())
+        .await
+        .unwrap();
+
+    assert_eq!(
+        *metrics.submissions.lock().unwrap(),
+        [SubmissionOutcome::Rejected, SubmissionOutcome::Accepted]
+    );
+
+    let requests = metrics.requests.lock().unwrap();
+    let summary: Vec<_> = requests
+        .iter()
+        .map(|request| (request.method.as_str(), request.endpoint.as_str(), request.status))
+        .collect();
+    assert_eq!(
+        summary,
+        [
+            ("POST", "/api/jobs/", Some(503)),
+            ("POST", "/api/jobs/", Some(200)),
+            ("GET", "/api/jobs/{submission_id}", Some(200)),
+            ("PUT", "/api/packages/gcs/{package}", Some(200)),
+        ]
+    );
+    let upload_bytes: Vec<_> = requests.iter().map(|request| request.upload_bytes).collect();
+    assert_eq!(upload_bytes[2..], [None, Some(7)]);
+    assert!(upload_bytes[1].is_some());
+}
+
+#[tokio::test]
+async fn test_mock_run_job_with_retry() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    let preempted = JobScript::failing()
+        .with_error_type("JOB_SUPERVISOR_ACTOR_DIED")
+        .with_message("The node running the job died");
+    mock.script_job("retry-job", preempted.clone());
+    mock.script_job("retry-job-retry-1", preempted);
+    let client = mock.client();
+
+    let policy = JobRetryPolicy::new(3)
+        .with_backoff(Duration::from_millis(1), Duration::from_millis(10))
+        .retry_on_message(|message| message.to_lowercase().contains("died"));
+    let metrics = Arc::new(RecordingMetrics::default());
+    let client = client.with_metrics_recorder(metrics.clone());
+    let payload = JobSubmitRequest::new("python train.py").with_submission_id("retry-job");
+    let options = RunOptions::new().with_wait_options(wait_options());
+
+    let report = client.run_job_with_retry(&payload, options, &policy).await;
+    assert!(report.is_success());
+    assert_eq!(report.retries(), 2);
+    let submission_ids: Vec<_> = report
+        .attempts
+        .iter()
+        .map(|attempt| attempt.submission_id.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        submission_ids,
+        ["retry-job", "retry-job-retry-1", "retry-job-retry-2"]
+    );
+    assert_eq!(
+        report.attempts[0].outcome.as_ref().unwrap().status(),
+        JobStatus::FAILED
+    );
+
+    mock.script_job("no-retry-job", JobScript::failing().with_message("Out of memory"));
+    let payload = JobSubmitRequest::new("python train.py").with_submission_id("no-retry-job");
+    let options = RunOptions::new().with_wait_options(wait_options());
+    let report = client.run_job_with_retry(&payload, options, &policy).await;
+    assert_eq!(report.retries(), 0);
+    assert_eq!(*metrics.retries.lock().unwrap(), [2, 0]);
+    assert!(matches!(
+        report.into_result(),
+        Err(Error::JobFailed {
+            status: JobStatus::FAILED,
+            ..
+        })
+    ));
+}
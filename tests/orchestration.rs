@@ -0,0 +1,71 @@
+use std::time::Duration;
+
+use ray_dashboard_sdk::{
+    RunOptions, WaitOptions,
+    orchestration::{JobGraph, NodeOutcome},
+    schemas::jobs::{JobStatus, JobSubmitRequest},
+    testing::{JobScript, MockRayDashboard},
+};
+
+fn job(submission_id: &str) -> JobSubmitRequest {
+    JobSubmitRequest::new(format!("python {}.py", submission_id)).with_submission_id(submission_id)
+}
+
+fn run_options() -> RunOptions {
+    let wait = WaitOptions::new()
+        .with_initial_interval(Duration::from_millis(10))
+        .with_timeout(Duration::from_secs(5));
+    RunOptions::new().with_wait_options(wait)
+}
+
+#[tokio::test]
+async fn test_graph_runs_in_dependency_order() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    let client = mock.client();
+
+    let graph = JobGraph::new()
+        .with_job("preprocess", job("graph-preprocess"))
+        .with_job("train", job("graph-train"))
+        .with_job("evaluate", job("graph-evaluate"))
+        .with_dependency("train", "preprocess")
+        .with_dependency("evaluate", "train");
+
+    let report = graph.run(&client, 2, run_options()).await.unwrap();
+    assert!(report.is_success());
+
+    let start_time = |id: &str| mock.job(id).unwrap().start_time.unwrap();
+    let end_time = |id: &str| mock.job(id).unwrap().end_time.unwrap();
+    assert!(start_time("graph-train") >= end_time("graph-preprocess"));
+    assert!(start_time("graph-evaluate") >= end_time("graph-train"));
+}
+
+#[tokio::test]
+async fn test_graph_skips_downstream_of_failed_job() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.script_job("graph-fail-train", JobScript::failing());
+    let client = mock.client();
+
+    let graph = JobGraph::new()
+        .with_job("preprocess", job("graph-fail-preprocess"))
+        .with_job("train", job("graph-fail-train"))
+        .with_job("evaluate", job("graph-fail-evaluate"))
+        .with_job("report", job("graph-fail-report"))
+        .with_dependency("train", "preprocess")
+        .with_dependency("evaluate", "train")
+        .with_dependency("report", "preprocess");
+
+    let report = graph.run(&client, 4, run_options()).await.unwrap();
+    assert!(!report.is_success());
+    assert!(report.get("report").unwrap().is_success());
+
+    let train = report.get("train").unwrap();
+    assert!(matches!(train, NodeOutcome::Failed(_)));
+    assert_eq!(train.job_outcome().unwrap().status(), JobStatus::FAILED);
+    assert!(matches!(
+        report.get("evaluate").unwrap(),
+        NodeOutcome::Skipped { dependency } if dependency == "train"
+    ));
+    assert!(mock.job("graph-fail-evaluate").is_none());
+    assert_eq!(report.succeeded().count(), 2);
+    assert_eq!(report.skipped().count(), 1);
+}
@@ -0,0 +1,138 @@
+use std::time::Duration;
+
+use ray_dashboard_sdk::{
+    Error, JobRetryPolicy, JobSubmissionAPI, RayClusterPool, RunOptions, WaitOptions,
+    pool::{LeastPendingJobs, MostFreeGpus, PoolCluster, PoolJobId},
+    schemas::jobs::{JobStatus, JobSubmitRequest},
+    testing::{JobScript, MockRayDashboard},
+};
+
+async fn start_mocks() -> (MockRayDashboard, MockRayDashboard) {
+    let east = MockRayDashboard::start().await.unwrap();
+    let west = MockRayDashboard::start().await.unwrap();
+    (east, west)
+}
+
+fn pool(east: &MockRayDashboard, west: &MockRayDashboard) -> RayClusterPool {
+    RayClusterPool::new()
+        .with_cluster(PoolCluster::new("east", east.client()).unwrap())
+        .with_cluster(PoolCluster::new("west", west.client()).unwrap())
+}
+
+#[tokio::test]
+async fn test_pool_round_robin_routes_by_job_id() {
+    let (east, west) = start_mocks().await;
+    let pool = pool(&east, &west);
+
+    let payload = JobSubmitRequest::new("echo 'ABC123'");
+    let first = pool.submit_job(&payload).await.unwrap().submission_id;
+    let second = pool.submit_job(&payload).await.unwrap().submission_id;
+
+    let first: PoolJobId = first.parse().unwrap();
+    let second: PoolJobId = second.parse().unwrap();
+    assert_eq!(first.cluster, "east");
+    assert_eq!(second.cluster, "west");
+    assert_eq!(east.jobs().len(), 1);
+    assert_eq!(west.jobs().len(), 1);
+
+    let options = WaitOptions::new().with_initial_interval(Duration::from_millis(10));
+    let details = pool
+        .wait_for_terminal_with_options(&second.to_string(), &options)
+        .await
+        .unwrap();
+    assert_eq!(details.status, JobStatus::SUCCEEDED);
+    assert_eq!(details.submission_id, Some(second.to_string()));
+
+    let jobs = pool.list_jobs().await.unwrap();
+    assert_eq!(jobs.len(), 2);
+
+    let error = pool.get_job_status("north:raysubmit_123").await.unwrap_err();
+    assert!(matches!(error, Error::UnknownCluster(ref name) if name == "north"));
+}
+
+#[tokio::test]
+async fn test_pool_least_pending_jobs() {
+    let (east, west) = start_mocks().await;
+    east.set_default_script(JobScript::running());
+    east.client()
+        .submit_job(&JobSubmitRequest::new("sleep 60"))
+        .await
+        .unwrap();
+    let pool = pool(&east, &west).with_selector(LeastPendingJobs);
+
+    let cluster = pool.select(&JobSubmitRequest::new("echo")).await.unwrap();
+    assert_eq!(cluster.name(), "west");
+}
+
+#[tokio::test]
+async fn test_pool_most_free_gpus() {
+    let (east, west) = start_mocks().await;
+    east.set_resource_usage("GPU", 3.0, 4.0);
+    west.set_resource_usage("GPU", 1.0, 4.0);
+    let pool = pool(&east, &west).with_selector(MostFreeGpus);
+
+    let cluster = pool.select(&JobSubmitRequest::new("echo")).await.unwrap();
+    assert_eq!(cluster.name(), "west");
+
+    let mut payload = JobSubmitRequest::new("python train.py");
+    payload.entrypoint_num_gpus = Some(8.0);
+    let error = pool.select(&payload).await.unwrap_err();
+    assert!(matches!(error, Error::NoClusterAvailable(_)));
+}
+
+#[tokio::test]
+async fn test_pool_run_job_stops_on_cancel() {
+    let (east, west) = start_mocks().await;
+    east.set_default_script(JobScript::running());
+    let pool = pool(&east, &west);
+
+    let options = RunOptions::new()
+        .with_wait_options(WaitOptions::new().with_initial_interval(Duration::from_millis(10)))
+        .with_stop_on_cancel(true);
+    let payload = JobSubmitRequest::new("sleep 60");
+    let run = pool.run_job(&payload, options.clone());
+    assert!(
+        tokio::time::timeout(Duration::from_millis(100), run)
+            .await
+            .is_err()
+    );
+    tokio::time::sleep(Duration::from_millis(100)).await;
+    assert_eq!(east.jobs()[0].status, JobStatus::STOPPED);
+
+    let outcome = pool
+        .run_job(&JobSubmitRequest::new("echo 'ABC123'"), options)
+        .await
+        .unwrap();
+    let job_id: PoolJobId = outcome.submission_id.parse().unwrap();
+    assert_eq!(job_id.cluster, "west");
+    assert_eq!(outcome.details.submission_id, Some(outcome.submission_id.clone()));
+}
+
+#[tokio::test]
+async fn test_pool_run_job_with_retry_ids() {
+    let (east, west) = start_mocks().await;
+    east.set_default_script(JobScript::failing());
+    west.set_default_script(JobScript::failing());
+    let pool = pool(&east, &west);
+
+    let options = RunOptions::new()
+        .with_wait_options(WaitOptions::new().with_initial_interval(Duration::from_millis(10)));
+    let policy = JobRetryPolicy::new(2).with_backoff(Duration::ZERO, Duration::ZERO);
+    let payload = JobSubmitRequest::new("python train.py").with_submission_id("train");
+    let report = pool.run_job_with_retry(&payload, options, &policy).await;
+
+    let submission_ids: Vec<_> = report
+        .attempts
+        .iter()
+        .map(|attempt| attempt.submission_id.as_deref().unwrap())
+        .collect();
+    assert_eq!(
+        submission_ids,
+        ["east:train", "west:train-retry-1", "east:train-retry-2"]
+    );
+    assert!(
+        east.jobs()
+            .iter()
+            .any(|job| job.submission_id.as_deref() == Some("train-retry-2"))
+    );
+}
@@ -0,0 +1,129 @@
+use axum::http::{Method, StatusCode};
+use futures::StreamExt;
+use ray_dashboard_sdk::{
+    SubmissionQueue,
+    queue::{QUEUE_METADATA_KEY, QueueBlock, QueueEvent},
+    schemas::jobs::{JobStatus, JobSubmitRequest},
+    testing::{InjectedFailure, JobScript, MockRayDashboard},
+};
+
+fn job(submission_id: &str, gpus: Option<f64>) -> JobSubmitRequest {
+    let request = JobSubmitRequest::new("python train.py").with_submission_id(submission_id);
+    match gpus {
+        Some(gpus) => request.with_entrypoint_num_gpus(gpus),
+        None => request,
+    }
+}
+
+fn ids(events: &[QueueEvent]) -> Vec<String> {
+    events
+        .iter()
+        .map(|event| match event {
+            QueueEvent::Enqueued { submission_id, .. } => format!("enqueued:{}", submission_id),
+            QueueEvent::Blocked { submission_id, .. } => format!("blocked:{}", submission_id),
+            QueueEvent::Submitted { submission_id } => format!("submitted:{}", submission_id),
+            QueueEvent::Failed { submission_id, .. } => format!("failed:{}", submission_id),
+            QueueEvent::Finished { submission_id, .. } => format!("finished:{}", submission_id),
+            QueueEvent::Removed { submission_id } => format!("removed:{}", submission_id),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn test_queue_respects_limits_and_priority() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.set_default_script(JobScript::running());
+    let client = mock.client();
+
+    let queue = SubmissionQueue::new("training")
+        .with_max_active(3)
+        .with_gpu_quota(4.0);
+    let events = queue.subscribe();
+
+    queue.push(job("a", Some(2.0)), 0).unwrap();
+    queue.push(job("b", Some(2.0)), 0).unwrap();
+    queue.push(job("c", Some(1.0)), 10).unwrap();
+    queue.push(job("d", None), 0).unwrap();
+    queue.push(job("e", None), 0).unwrap();
+
+    let first = queue.tick(&client).await.unwrap();
+    assert_eq!(ids(&first), ["submitted:c", "submitted:a", "blocked:b"]);
+    assert!(matches!(
+        &first[2],
+        QueueEvent::Blocked { reason: QueueBlock::GpuQuota { used, .. }, .. } if *used == 3.0
+    ));
+    assert_eq!(
+        mock.job("a").unwrap().metadata.unwrap()[QUEUE_METADATA_KEY],
+        "training"
+    );
+
+    // Nothing changed, so the blocked event is not repeated
+    assert!(queue.tick(&client).await.unwrap().is_empty());
+
+    mock.set_job_status("a", JobStatus::SUCCEEDED);
+    let second = queue.tick(&client).await.unwrap();
+    assert_eq!(
+        ids(&second),
+        ["finished:a", "submitted:b", "submitted:d", "blocked:e"]
+    );
+    assert!(matches!(
+        &second[3],
+        QueueEvent::Blocked {
+            reason: QueueBlock::MaxActive { limit: 3 },
+            ..
+        }
+    ));
+    assert_eq!(queue.pending(), ["e"]);
+
+    let usage = queue.usage(&client).await.unwrap();
+    assert_eq!((usage.active, usage.gpus), (3, 3.0));
+    assert!(queue.remove("e"));
+
+    drop(queue);
+    let all: Vec<QueueEvent> = events.collect().await;
+    assert_eq!(
+        ids(&all),
+        [
+            "enqueued:a",
+            "enqueued:b",
+            "enqueued:c",
+            "enqueued:d",
+            "enqueued:e",
+            "submitted:c",
+            "submitted:a",
+            "blocked:b",
+            "finished:a",
+            "submitted:b",
+            "submitted:d",
+            "blocked:e",
+            "removed:e",
+        ]
+    );
+}
+
+#[tokio::test]
+async fn test_queue_keeps_jobs_on_server_errors() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    let client = mock.client();
+    let queue = SubmissionQueue::new("training");
+    queue.push(job("a", None), 0).unwrap();
+    queue.push(job("b", None), 0).unwrap();
+
+    mock.inject_failure(
+        InjectedFailure::new("/api/jobs/")
+            .with_method(Method::POST)
+            .with_times(1),
+    );
+    assert!(queue.tick(&client).await.unwrap().is_empty());
+    assert_eq!(queue.pending(), ["a", "b"]);
+
+    mock.inject_failure(
+        InjectedFailure::new("/api/jobs/")
+            .with_method(Method::POST)
+            .with_status(StatusCode::BAD_REQUEST)
+            .with_times(1),
+    );
+    let events = queue.tick(&client).await.unwrap();
+    assert_eq!(ids(&events), ["failed:a", "submitted:b"]);
+    assert!(queue.is_empty());
+}
@@ -0,0 +1,60 @@
+use std::sync::Arc;
+
+use ray_dashboard_sdk::{
+    JobSubmissionAPI,
+    registry::JobRegistry,
+    schemas::jobs::{JobStatus, JobSubmitRequest},
+    testing::{JobScript, MockRayDashboard},
+};
+
+#[tokio::test]
+async fn test_registry_records_and_reconciles() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.script_job("registry-failed", JobScript::failing().with_exit_code(3));
+    mock.script_job("registry-running", JobScript::running());
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    let path = temp_dir.path().join("jobs.json");
+    let registry = Arc::new(JobRegistry::open(&path).unwrap());
+    let client = mock.client().with_registry(registry.clone());
+
+    for submission_id in ["registry-failed", "registry-running"] {
+        let payload = JobSubmitRequest::new("python train.py").with_submission_id(submission_id);
+        client.submit_job(&payload).await.unwrap();
+    }
+    let cluster_url = client.base_url().as_str();
+    let entry = registry.get(cluster_url, "registry-failed").unwrap();
+    assert_eq!(entry.cluster_url, client.base_url().as_str());
+    assert_eq!(entry.status(), JobStatus::PENDING);
+
+    mock.set_job_status("registry-failed", JobStatus::FAILED);
+    mock.set_job_status("registry-running", JobStatus::RUNNING);
+    let report = registry.reconcile(&client).await.unwrap();
+    assert_eq!(report.updated.len(), 2);
+    assert!(report.missing.is_empty());
+
+    let registry = JobRegistry::open(&path).unwrap();
+    let failed = registry.get(cluster_url, "registry-failed").unwrap();
+    assert_eq!(failed.status(), JobStatus::FAILED);
+    assert_eq!(failed.details.driver_exit_code, Some(3));
+    assert!(failed.reconciled_at.is_some());
+    assert_eq!(
+        registry.get(cluster_url, "registry-running").unwrap().status(),
+        JobStatus::RUNNING
+    );
+
+    mock.set_job_status("registry-running", JobStatus::STOPPED);
+    client.delete_job("registry-running").await.unwrap();
+    let report = registry.reconcile(&client).await.unwrap();
+    assert!(report.updated.is_empty());
+    assert_eq!(report.missing, ["registry-running"]);
+
+    let report = registry.reconcile(&client).await.unwrap();
+    assert!(report.missing.is_empty());
+    let lost = JobRegistry::open(&path)
+        .unwrap()
+        .get(cluster_url, "registry-running")
+        .unwrap();
+    assert!(lost.lost);
+    assert_eq!(lost.status(), JobStatus::RUNNING);
+}
@@ -0,0 +1,155 @@
+//! Replays recorded dashboard responses from `tests/_fixtures`, so these tests run without Ray.
+//! Record the fixtures again against `docker compose up` with `RAY_FIXTURE_MODE=record`.
+
+mod common;
+
+use std::{fs, path::Path, sync::Arc, time::Duration};
+
+use ray_dashboard_sdk::{
+    JobSubmissionAPI, PackagesAPI, RayDashboardClient, RunOptions,
+    schemas::{
+        env::RuntimeEnv,
+        jobs::{JobStatus, JobSubmitRequest},
+    },
+    testing::HttpFixture,
+};
+
+fn fixture_client(name: &str) -> RayDashboardClient {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/_fixtures")
+        .join(format!("{}.json", name));
+    let fixture = HttpFixture::from_env(path).expect("Fixture exists");
+    let url = std::env::var("RAY_DASHBOARD_URL").unwrap_or_else(|_| common::RAY_DASHBOARD_URL.to_string());
+    RayDashboardClient::new(&url)
+        .unwrap()
+        .with_fixture(Arc::new(fixture))
+}
+
+#[tokio::test]
+async fn test_replay_submit_and_get_job() {
+    let client = fixture_client("jobs_submit_and_get");
+
+    let submission_id = "replay-submit-and-get";
+    let payload = JobSubmitRequest::new("echo Hello, World!")
+        .with_submission_id(submission_id)
+        .with_metadata_item("environment", "dev");
+
+    let response = client.submit_job(&payload).await.expect("Able to submit job");
+    assert_eq!(response.submission_id, submission_id);
+
+    let job_details = client
+        .get_job_details(submission_id)
+        .await
+        .expect("Able to get job details");
+    assert_eq!(job_details.entrypoint, "echo Hello, World!");
+    let job_metadata = job_details.metadata.as_ref().expect("Job has metadata");
+    assert_eq!(job_metadata.get("environment").map(String::as_str), Some("dev"));
+
+    let jobs = client.list_jobs().await.expect("Able to list jobs");
+    assert!(!jobs.is_empty());
+}
+
+#[tokio::test]
+async fn test_replay_stop_and_delete_job() {
+    let client = fixture_client("jobs_stop_and_delete");
+
+    let submission_id = "replay-stop-and-delete";
+    let payload = JobSubmitRequest::new("sleep 60").with_submission_id(submission_id);
+    client.submit_job(&payload).await.expect("Able to submit job");
+
+    client.stop_job(submission_id).await.expect("Able to stop job");
+    let details = client
+        .wait_for_terminal(submission_id, Some(Duration::from_secs(5)))
+        .await
+        .unwrap();
+    assert_eq!(details.status, JobStatus::STOPPED);
+
+    let response = client
+        .delete_job(submission_id)
+        .await
+        .expect("Able to delete job");
+    assert!(response.deleted);
+}
+
+#[tokio::test]
+async fn test_replay_get_job_logs() {
+    let client = fixture_client("jobs_get_logs");
+
+    let submission_id = "replay-get-logs";
+    let payload = JobSubmitRequest::new("echo 'ABC123'").with_submission_id(submission_id);
+    client.submit_job(&payload).await.expect("Able to submit job");
+
+    client
+        .wait_for_terminal(submission_id, Some(Duration::from_secs(5)))
+        .await
+        .unwrap();
+
+    let logs = client
+        .get_job_logs(submission_id)
+        .await
+        .expect("Able to get job logs");
+    assert!(logs.lines().any(|line| line.contains("ABC123")));
+}
+
+#[tokio::test]
+async fn test_replay_run_job() {
+    let client = fixture_client("jobs_run_job");
+
+    let payload = JobSubmitRequest::new("echo 'ABC123'").with_submission_id("replay-run-job");
+    let options = RunOptions::new().with_timeout(Duration::from_secs(30));
+
+    let outcome = client
+        .run_job(&payload, options)
+        .await
+        .expect("Able to run job")
+        .into_result()
+        .expect("Job succeeded");
+    assert_eq!(outcome.status(), JobStatus::SUCCEEDED);
+    assert!(outcome.logs.unwrap().contains("ABC123"));
+}
+
+#[tokio::test]
+async fn test_replay_submit_job_with_working_dir() {
+    let client = fixture_client("jobs_working_dir");
+
+    let working_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/_resources");
+    let runtime_env = RuntimeEnv::new().with_working_dir(&working_dir);
+
+    let submission_id = "replay-working-dir";
+    let payload = JobSubmitRequest::new("python script.py")
+        .with_submission_id(submission_id)
+        .with_runtime_env(runtime_env);
+    client.submit_job(&payload).await.expect("Able to submit job");
+
+    let details = client
+        .wait_for_terminal(submission_id, Some(Duration::from_secs(30)))
+        .await
+        .unwrap();
+    assert_eq!(details.status, JobStatus::SUCCEEDED);
+
+    let logs = client.get_job_logs(submission_id).await.unwrap();
+    assert!(
+        logs.lines()
+            .any(|line| line.contains("Hello from working directory!"))
+    );
+}
+
+#[tokio::test]
+async fn test_replay_upload_and_check_package() {
+    let client = fixture_client("packages_upload_and_check");
+
+    let temp_dir = tempfile::tempdir().unwrap();
+    fs::write(temp_dir.path().join("file1.txt"), "content1").unwrap();
+    fs::write(temp_dir.path().join("file2.txt"), "content2").unwrap();
+
+    let uri1 = client.upload_directory_if_needed(temp_dir.path()).await.unwrap();
+    let uri2 = client.upload_directory_if_needed(temp_dir.path()).await.unwrap();
+    assert_eq!(uri1, uri2);
+    assert!(client.package_exists(&uri1).await.unwrap());
+
+    let exists = client
+        .package_exists("gcs://_ray_pkg_nonexistent.zip")
+        .await
+        .unwrap();
+    assert!(!exists);
+}
@@ -0,0 +1,132 @@
+use std::time::Duration;
+
+use chrono::{DateTime, TimeDelta, Utc};
+use ray_dashboard_sdk::{
+    scheduler::{OverlapPolicy, ScheduleEvent, ScheduledJob, Scheduler, Trigger},
+    schemas::jobs::{JobStatus, JobSubmitRequest},
+    testing::{JobScript, MockRayDashboard},
+};
+
+fn start() -> DateTime<Utc> {
+    "2025-06-01T00:00:00Z".parse().unwrap()
+}
+
+fn scheduler(overlap: OverlapPolicy) -> Scheduler {
+    let job = ScheduledJob::new(
+        "hourly",
+        Trigger::interval(Duration::from_secs(3600)),
+        JobSubmitRequest::new("python batch.py"),
+    )
+    .with_overlap_policy(overlap);
+    Scheduler::new().with_job(job)
+}
+
+async fn tick_hours(scheduler: &mut Scheduler, mock: &MockRayDashboard, hours: i64) -> Vec<ScheduleEvent> {
+    scheduler
+        .tick(&mock.client(), start() + TimeDelta::hours(hours))
+        .await
+}
+
+#[tokio::test]
+async fn test_scheduler_submits_with_templated_ids() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    let mut scheduler = scheduler(OverlapPolicy::Skip);
+
+    assert!(tick_hours(&mut scheduler, &mock, 0).await.is_empty());
+    assert_eq!(scheduler.next_run(), Some(start() + TimeDelta::hours(1)));
+
+    let events = tick_hours(&mut scheduler, &mock, 1).await;
+    assert!(matches!(
+        events.as_slice(),
+        [ScheduleEvent::Submitted { submission_id, .. }] if submission_id == "hourly-20250601T010000Z"
+    ));
+    assert!(mock.job("hourly-20250601T010000Z").is_some());
+}
+
+#[tokio::test]
+async fn test_scheduler_skips_overlapping_run() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.set_default_script(JobScript::running());
+    let mut scheduler = scheduler(OverlapPolicy::Skip);
+
+    tick_hours(&mut scheduler, &mock, 0).await;
+    tick_hours(&mut scheduler, &mock, 1).await;
+    let events = tick_hours(&mut scheduler, &mock, 2).await;
+    assert!(matches!(
+        events.as_slice(),
+        [ScheduleEvent::Skipped { active, .. }] if active == "hourly-20250601T010000Z"
+    ));
+    assert_eq!(mock.jobs().len(), 1);
+}
+
+#[tokio::test]
+async fn test_scheduler_queues_overlapping_run() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.set_default_script(JobScript::running());
+    let mut scheduler = scheduler(OverlapPolicy::Queue);
+
+    tick_hours(&mut scheduler, &mock, 0).await;
+    tick_hours(&mut scheduler, &mock, 1).await;
+    let events = tick_hours(&mut scheduler, &mock, 2).await;
+    assert!(matches!(events.as_slice(), [ScheduleEvent::Queued { .. }]));
+    let events = tick_hours(&mut scheduler, &mock, 3).await;
+    assert!(matches!(
+        events.as_slice(),
+        [ScheduleEvent::Skipped { active, .. }] if active == "hourly-20250601T010000Z"
+    ));
+
+    mock.set_job_status("hourly-20250601T010000Z", JobStatus::SUCCEEDED);
+    let events = scheduler
+        .tick(&mock.client(), start() + TimeDelta::minutes(210))
+        .await;
+    assert!(matches!(
+        events.as_slice(),
+        [ScheduleEvent::Submitted { submission_id, .. }] if submission_id == "hourly-20250601T020000Z"
+    ));
+    assert_eq!(mock.jobs().len(), 2);
+}
+
+#[tokio::test]
+async fn test_scheduler_submits_queued_run_first() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    // The first run finishes between the queued run's check and the next run firing
+    mock.script_job(
+        "hourly-20250601T010000Z",
+        JobScript::new([JobStatus::RUNNING, JobStatus::RUNNING, JobStatus::SUCCEEDED]),
+    );
+    let mut scheduler = scheduler(OverlapPolicy::Queue);
+
+    tick_hours(&mut scheduler, &mock, 0).await;
+    tick_hours(&mut scheduler, &mock, 1).await;
+    tick_hours(&mut scheduler, &mock, 2).await;
+    let events = tick_hours(&mut scheduler, &mock, 3).await;
+    assert!(matches!(events.as_slice(), [ScheduleEvent::Skipped { .. }]));
+
+    let events = scheduler
+        .tick(&mock.client(), start() + TimeDelta::minutes(210))
+        .await;
+    assert!(matches!(
+        events.as_slice(),
+        [ScheduleEvent::Submitted { submission_id, .. }] if submission_id == "hourly-20250601T020000Z"
+    ));
+}
+
+#[tokio::test]
+async fn test_scheduler_replaces_overlapping_run() {
+    let mock = MockRayDashboard::start().await.unwrap();
+    mock.set_default_script(JobScript::running());
+    let mut scheduler = scheduler(OverlapPolicy::Replace);
+
+    tick_hours(&mut scheduler, &mock, 0).await;
+    tick_hours(&mut scheduler, &mock, 1).await;
+    let events = tick_hours(&mut scheduler, &mock, 2).await;
+    assert!(matches!(
+        events.as_slice(),
+        [ScheduleEvent::Replaced { stopped, submission_id, .. }]
+            if stopped == "hourly-20250601T010000Z" && submission_id == "hourly-20250601T020000Z"
+    ));
+    assert_eq!(
+        mock.job("hourly-20250601T010000Z").unwrap().status,
+        JobStatus::STOPPED
+    );
+}